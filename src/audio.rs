@@ -8,10 +8,12 @@ use iyes_loopless::{
 
 use crate::{
     game::{
-        actions::{OnCrateSplashedInWater, OnDropCrateOnShip},
+        actions::{OnCrateShattered, OnCrateSplashedInWater, OnDropCrateOnShip},
         components::{HardSurface, HardSurfaceHandled, PhysicsCrate},
         factory::events::OnIncorrectFactoryRecipeEffects,
-        OnCoinsReceived, OnRainEnd, OnRainStart, OnShipSpawned,
+        warehouse::OnWarehouseOverflow,
+        weather::{OnWeatherRestored, Weather},
+        OnCoinsReceived, OnRainEnd, OnRainStart, OnShipFoundered, OnShipSpawned,
     },
     loader::AudioAssets,
     GameState,
@@ -43,11 +45,26 @@ impl Plugin for InternalAudioPlugin {
                     .run_in_state(GameState::Playing)
                     .run_on_event::<OnDropCrateOnShip>(),
             )
+            .add_system(
+                on_box_drop
+                    .run_in_state(GameState::Playing)
+                    .run_on_event::<OnWarehouseOverflow>(),
+            )
+            .add_system(
+                on_box_drop
+                    .run_in_state(GameState::Playing)
+                    .run_on_event::<OnCrateShattered>(),
+            )
             .add_system(
                 on_splash
                     .run_in_state(GameState::Playing)
                     .run_on_event::<OnCrateSplashedInWater>(),
             )
+            .add_system(
+                on_splash
+                    .run_in_state(GameState::Playing)
+                    .run_on_event::<OnShipFoundered>(),
+            )
             .add_system(
                 on_incorrect_production
                     .run_in_state(GameState::Playing)
@@ -63,6 +80,11 @@ impl Plugin for InternalAudioPlugin {
                     .run_in_state(GameState::Playing)
                     .run_on_event::<OnRainStart>(),
             )
+            .add_system(
+                on_weather_restored
+                    .run_in_state(GameState::Playing)
+                    .run_on_event::<OnWeatherRestored>(),
+            )
             .add_system(
                 on_rain_stop
                     .run_in_state(GameState::Playing)
@@ -136,6 +158,18 @@ fn on_rain_start(rain_channel: Res<AudioChannel<RainChannel>>, audio_assets: Res
     rain_channel.play(audio_assets.rain.clone());
 }
 
+/// Picks the rain sound back up when a game saved in a storm is restored
+fn on_weather_restored(
+    mut restored_events: EventReader<OnWeatherRestored>,
+    rain_channel: Res<AudioChannel<RainChannel>>,
+    audio_assets: Res<AudioAssets>,
+) {
+    if restored_events.iter().any(|evt| evt.0 == Weather::Stormy) {
+        info!("Playing rain sound");
+        rain_channel.play(audio_assets.rain.clone());
+    }
+}
+
 fn on_rain_stop(rain_channel: Res<AudioChannel<RainChannel>>) {
     info!("Stopping rain sound");
     rain_channel.stop();
@@ -1,40 +1,70 @@
 use bevy::prelude::*;
-use heron::Collisions;
+use heron::{Collisions, Velocity};
 
 use crate::{
     game::{
-        components::{FactoryInput, PhysicsCrate, ShipHold, SplashCatcher, Wave},
-        factory::OnDropInFactoryInput,
+        clock::GameClock,
+        components::{FactoryInput, PhysicsCrate, ShipHold, SplashCatcher, Wave, WetCrate},
+        factory::{events::OnIncorrectFactoryRecipeEffects, OnDropInFactoryInput},
     },
     HEIGHT, WIDTH,
 };
 
-use super::dropping::OnDropCrateOnShip;
+use super::{dropping::OnDropCrateOnShip, floating::start_floating};
 
 pub struct OnCrateSplashedInWater(pub Vec2);
 
+/// How fast rotten crates are thrown back out of the factory input
+const ROTTEN_CRATE_REJECT_VELOCITY: Vec3 = Vec3::new(-150.0, 200.0, 0.0);
+
 /// Handles collisions between physics crates and ships
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn detect_crate_drop_on_ship(
     mut commands: Commands,
+    time: Res<GameClock>,
     mut factory_event: EventWriter<OnDropInFactoryInput>,
+    mut rejected_event: EventWriter<OnIncorrectFactoryRecipeEffects>,
     mut drop_on_ship_event: EventWriter<OnDropCrateOnShip>,
     mut splash_event: EventWriter<OnCrateSplashedInWater>,
-    box_collisions: Query<(Entity, &Collisions, &PhysicsCrate, &Transform)>,
+    mut box_collisions: Query<(
+        Entity,
+        &Collisions,
+        &PhysicsCrate,
+        &Transform,
+        &mut Velocity,
+        Option<&WetCrate>,
+    )>,
     ship_entities: Query<&Children, With<Wave>>,
     factory_inputs: Query<&FactoryInput>,
     splashers: Query<&SplashCatcher>,
     mut ship_holds: Query<(Entity, &mut ShipHold, &GlobalTransform)>,
 ) {
-    for (crate_entity, crate_collisions, physics_crate, crate_tx) in box_collisions.iter() {
+    let now = time.seconds_since_startup();
+
+    for (crate_entity, crate_collisions, physics_crate, crate_tx, mut velocity, wet) in
+        box_collisions.iter_mut()
+    {
+        let is_rotten = physics_crate.is_rotten(now);
+
         for collision in crate_collisions.entities() {
             if factory_inputs.get(collision).is_ok() {
+                if is_rotten {
+                    // throw it back out, unless that has already happened
+                    if velocity.linear.y <= 0.0 {
+                        info!("Rotten {:?} rejected by factory", physics_crate.box_type);
+                        velocity.linear = ROTTEN_CRATE_REJECT_VELOCITY;
+                        rejected_event.send(OnIncorrectFactoryRecipeEffects);
+                    }
+                    continue;
+                }
+
                 info!(
                     "Dropped {:?} in factory input, raising event and despawning",
                     physics_crate.box_type
                 );
                 factory_event.send(OnDropInFactoryInput {
                     box_type: physics_crate.box_type,
+                    spawned_at: physics_crate.spawned_at,
                 });
                 commands.entity(crate_entity).despawn_recursive();
                 continue;
@@ -43,8 +73,8 @@ pub fn detect_crate_drop_on_ship(
             if splashers.get(collision).is_ok() {
                 info!("Crate splashed down!");
                 splash_event.send(OnCrateSplashedInWater(crate_tx.translation.truncate()));
-                commands.entity(crate_entity).despawn_recursive();
-                continue;
+                start_floating(&mut commands, crate_entity, physics_crate, velocity.linear);
+                break;
             }
 
             if let Ok(children) = ship_entities.get(collision) {
@@ -53,15 +83,21 @@ pub fn detect_crate_drop_on_ship(
                     if let Ok((ship_entity, mut ship_hold, tx)) = ship_holds.get_mut(*child) {
                         info!("Crate {:?} dropped on ship {:?}!", crate_entity, ship_hold);
 
+                        // rotten crates are worthless, so they can't meet a demand
                         let unmet_demands = ship_hold.get_unmet_demands();
                         drop_on_ship_event.send(OnDropCrateOnShip {
                             ship_entity,
                             box_type: physics_crate.box_type,
                             location: tx.translation(),
-                            was_demanded: unmet_demands.contains(&physics_crate.box_type),
+                            was_demanded: !is_rotten
+                                && unmet_demands.contains(&physics_crate.box_type),
+                            was_rotten: is_rotten,
+                            was_wet: wet.is_some(),
                         });
 
-                        ship_hold.crates.push(physics_crate.box_type);
+                        if !is_rotten {
+                            ship_hold.crates.push(physics_crate.box_type);
+                        }
 
                         commands.entity(crate_entity).despawn_recursive();
                     }
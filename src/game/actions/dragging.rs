@@ -1,35 +1,50 @@
 use bevy::prelude::*;
 
 use crate::{
-    game::components::{BoxType, Cart, CartCrate, FollowMouse, PhysicsCrate},
+    game::clock::GameClock,
+    game::components::{
+        BoxType, Cart, CartCrate, FloatingCrate, FollowMouse, PhysicsCrate, Pickable, WetCrate,
+    },
     input::MousePosition,
     loader::TextureAssets,
 };
 
+#[derive(Default)]
 pub struct DraggingBox {
     pub box_entity: Option<Entity>,
     pub box_type: Option<BoxType>,
-    pub is_front_slot: bool,
+
+    /// The time (since startup) the dragged crate first came off a cart
+    pub spawned_at: f64,
+
+    /// Whether the dragged crate has been fished out of the water
+    pub is_wet: bool,
 }
 
 pub enum StartDraggingItem {
     Cart(Entity),
     PhysicsCrate(Entity),
+    FloatingCrate(Entity),
 }
 
 pub struct OnStartDragging {
     pub dragged_entity: StartDraggingItem,
-    pub is_front_slot: bool,
+
+    /// The cargo slot being picked up, if dragging from a cart
+    pub slot: usize,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn start_dragging(
     mut commands: Commands,
+    time: Res<GameClock>,
     texture_assets: Res<TextureAssets>,
     mut dragging: ResMut<DraggingBox>,
     mut events: EventReader<OnStartDragging>,
     mut carts: Query<(&mut Cart, &Children)>,
-    mut child_crates: Query<(&mut Visibility, &CartCrate)>,
-    physics_crates: Query<&PhysicsCrate>,
+    mut child_crates: Query<(Entity, &mut Visibility, &CartCrate)>,
+    physics_crates: Query<(&PhysicsCrate, Option<&WetCrate>)>,
+    floating_crates: Query<&FloatingCrate>,
 ) {
     for event in events.iter() {
         match event.dragged_entity {
@@ -43,28 +58,23 @@ pub fn start_dragging(
                 };
 
                 // update the cart and set the dragging item here
-                dragging.box_type = if event.is_front_slot {
-                    let item = cart.front;
-                    cart.front = None;
-                    item
-                } else {
-                    let item = cart.back;
-                    cart.back = None;
-                    item
-                };
+                dragging.box_type = cart.slots.get_mut(event.slot).and_then(Option::take);
+                dragging.spawned_at = time.seconds_since_startup();
+                dragging.is_wet = false;
 
-                // hide the box that's being dragged
+                // hide the box that's being dragged, and stop it being picked again
                 for child in children.iter() {
                     // not all children are cart crates
-                    if let Ok((mut vis, child_crate)) = child_crates.get_mut(*child) {
-                        if child_crate.is_front_slot == event.is_front_slot {
+                    if let Ok((child_ent, mut vis, child_crate)) = child_crates.get_mut(*child) {
+                        if child_crate.slot == event.slot {
                             vis.is_visible = false;
+                            commands.entity(child_ent).remove::<Pickable>();
                         }
                     }
                 }
             }
             StartDraggingItem::PhysicsCrate(crate_ent) => {
-                let physics_crate = match physics_crates.get(crate_ent) {
+                let (physics_crate, wet) = match physics_crates.get(crate_ent) {
                     Ok(pc) => pc,
                     Err(e) => {
                         warn!("get physics crate: {:?}", e);
@@ -73,6 +83,23 @@ pub fn start_dragging(
                 };
 
                 dragging.box_type = Some(physics_crate.box_type);
+                dragging.spawned_at = physics_crate.spawned_at;
+                dragging.is_wet = wet.is_some();
+                commands.entity(crate_ent).despawn_recursive();
+            }
+            StartDraggingItem::FloatingCrate(crate_ent) => {
+                let floating = match floating_crates.get(crate_ent) {
+                    Ok(fc) => fc,
+                    Err(e) => {
+                        warn!("get floating crate: {:?}", e);
+                        continue;
+                    }
+                };
+
+                // fished out of the water in time, but soaked
+                dragging.box_type = Some(floating.box_type);
+                dragging.spawned_at = floating.spawned_at;
+                dragging.is_wet = true;
                 commands.entity(crate_ent).despawn_recursive();
             }
         }
@@ -82,8 +109,6 @@ pub fn start_dragging(
             continue;
         }
 
-        dragging.is_front_slot = event.is_front_slot;
-
         // spawn a box based on the box type and attach it to the mouse
         dragging.box_entity = Some(
             commands
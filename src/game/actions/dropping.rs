@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 
 use crate::{
-    game::{components::BoxType, spawners::spawn_physics_crate},
+    game::{
+        components::{BoxType, WetCrate},
+        spawners::spawn_physics_crate,
+    },
     input::MousePosition,
 };
 
@@ -20,6 +23,12 @@ pub struct OnDropCrateOnShip {
 
     /// Is the crate in the ship's demands
     pub was_demanded: bool,
+
+    /// Had the crate rotted before it was delivered
+    pub was_rotten: bool,
+
+    /// Had the crate been fished out of the water, reducing its value
+    pub was_wet: bool,
 }
 
 pub fn handle_drop(
@@ -34,11 +43,20 @@ pub fn handle_drop(
             &mut commands,
             dragging.box_entity.unwrap(),
             dragging.box_type.unwrap(),
+            dragging.spawned_at,
             mouse_position.velocity * CRATE_DROP_VELOCITY_FACTOR,
         );
 
+        if dragging.is_wet {
+            commands
+                .entity(dragging.box_entity.unwrap())
+                .insert(WetCrate);
+        }
+
         // reset the dragging state
         dragging.box_type = None;
         dragging.box_entity = None;
+        dragging.spawned_at = 0.0;
+        dragging.is_wet = false;
     }
 }
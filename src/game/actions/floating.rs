@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use heron::{
+    Acceleration, CollisionLayers, CollisionShape, Collisions, PhysicMaterial, RigidBody, Velocity,
+};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    game::{
+        clock::GameClock,
+        components::{
+            BoxType, FloatingCrate, PhysicsCrate, PickArea, PreviousVelocity, ShipHold, Sinking,
+            Wave,
+        },
+        wind::Wind,
+    },
+    GRID_SIZE, HEIGHT, WIDTH,
+};
+
+use super::dropping::OnDropCrateOnShip;
+
+/// The height crates float at, on top of the splash catcher
+const WATER_SURFACE_Y: f32 = -HEIGHT / 2.0 + GRID_SIZE;
+
+/// How long a crate floats before it is lost, including the time it takes to sink
+const FLOAT_SECONDS: f32 = 6.0;
+const SINK_SECONDS: f32 = 1.5;
+
+const BOB_AMOUNT: f32 = 3.0;
+const BOB_SPEED: f32 = 3.0;
+
+/// How much of a splashing crate's sideways speed it keeps while floating
+const FLOAT_DRIFT_FACTOR: f32 = 0.3;
+
+/// How quickly floating crates lose their sideways speed
+const FLOAT_DRAG: f32 = 0.5;
+
+/// How much of the wind speed pushes floating crates along
+const FLOAT_WIND_FACTOR: f32 = 0.4;
+
+/// How far below its collider a ship's hull reaches into the water
+const HULL_DEPTH: f32 = 0.5 * GRID_SIZE;
+
+/// Not a system - takes a crate that has hit the water out of the physics simulation and
+/// leaves it floating on the surface
+pub fn start_floating(
+    commands: &mut Commands,
+    crate_entity: Entity,
+    physics_crate: &PhysicsCrate,
+    velocity: Vec3,
+) {
+    commands.entity(crate_entity).remove_bundle::<(
+        PhysicsCrate,
+        RigidBody,
+        CollisionShape,
+        CollisionLayers,
+        PhysicMaterial,
+        Velocity,
+        PreviousVelocity,
+        Acceleration,
+        Collisions,
+    )>();
+
+    float_crate(
+        commands,
+        crate_entity,
+        physics_crate.box_type,
+        physics_crate.spawned_at,
+        velocity.x * FLOAT_DRIFT_FACTOR,
+        thread_rng().gen_range(0.0..std::f32::consts::TAU),
+        Duration::ZERO,
+    );
+}
+
+/// Not a system - leaves a crate floating on the surface, drifting at the given speed. Restored
+/// games pass in how long the crate had already been floating.
+pub fn float_crate(
+    commands: &mut Commands,
+    crate_entity: Entity,
+    box_type: BoxType,
+    spawned_at: f64,
+    drift: f32,
+    phase: f32,
+    floated_for: Duration,
+) {
+    let mut timer = Timer::from_seconds(FLOAT_SECONDS, false);
+    timer.set_elapsed(floated_for);
+
+    commands
+        .entity(crate_entity)
+        .insert(FloatingCrate {
+            box_type,
+            spawned_at,
+            drift,
+            phase,
+            timer,
+        })
+        // be generous, so crates can be grabbed as they bob about
+        .insert(PickArea(Vec2::splat(0.6 * GRID_SIZE)));
+}
+
+/// Bobs floating crates up and down as they drift with the wind, sinking them once
+/// they have been in the water too long
+pub fn float_crates(
+    mut commands: Commands,
+    time: Res<GameClock>,
+    wind: Res<Wind>,
+    mut crates: Query<(Entity, &mut FloatingCrate, &mut Transform)>,
+) {
+    let t = time.seconds_since_startup() as f32;
+    let dt = time.delta_seconds();
+
+    for (entity, mut floating, mut tx) in crates.iter_mut() {
+        floating.timer.tick(time.delta());
+
+        if floating.timer.finished() || tx.translation.x.abs() > 0.6 * WIDTH {
+            info!("Floating {:?} sank", floating.box_type);
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        floating.drift *= 1.0 - (FLOAT_DRAG * dt).clamp(0.0, 1.0);
+        tx.translation.x += (floating.drift + wind.velocity * FLOAT_WIND_FACTOR) * dt;
+
+        // sink slowly below the surface at the end
+        let remaining = (floating.timer.duration() - floating.timer.elapsed()).as_secs_f32();
+        let sunk = (1.0 - remaining / SINK_SECONDS).clamp(0.0, 1.0) * GRID_SIZE;
+
+        tx.translation.y =
+            WATER_SURFACE_Y + BOB_AMOUNT * (BOB_SPEED * t + floating.phase).sin() - sunk;
+        tx.rotation = Quat::from_rotation_z(0.1 * (0.5 * BOB_SPEED * t + floating.phase).cos());
+    }
+}
+
+/// Lets ships sailing past scoop floating crates up with their hulls
+#[allow(clippy::type_complexity)]
+pub fn catch_floating_crates(
+    mut commands: Commands,
+    time: Res<GameClock>,
+    mut drop_on_ship_event: EventWriter<OnDropCrateOnShip>,
+    crates: Query<(Entity, &FloatingCrate, &Transform)>,
+    ships: Query<(&GlobalTransform, &CollisionShape, &Children), (With<Wave>, Without<Sinking>)>,
+    mut ship_holds: Query<(Entity, &mut ShipHold, &GlobalTransform)>,
+) {
+    let now = time.seconds_since_startup();
+
+    for (crate_entity, floating, crate_tx) in crates.iter() {
+        let caught_by = ships.iter().find(|(ship_tx, shape, _)| {
+            let half_extends = match shape {
+                CollisionShape::Cuboid { half_extends, .. } => *half_extends,
+                _ => return false,
+            };
+
+            let offset = crate_tx.translation - ship_tx.translation();
+            offset.x.abs() <= half_extends.x
+                && offset.y >= -half_extends.y - HULL_DEPTH
+                && offset.y <= half_extends.y
+        });
+
+        let children = match caught_by {
+            Some((_, _, children)) => children,
+            None => continue,
+        };
+
+        for child in children.iter() {
+            if let Ok((ship_entity, mut ship_hold, tx)) = ship_holds.get_mut(*child) {
+                info!(
+                    "Floating {:?} caught by ship {:?}",
+                    floating.box_type, ship_entity
+                );
+
+                let is_rotten = floating.box_type.spoilage(now - floating.spawned_at) >= 1.0;
+                let unmet_demands = ship_hold.get_unmet_demands();
+                drop_on_ship_event.send(OnDropCrateOnShip {
+                    ship_entity,
+                    box_type: floating.box_type,
+                    location: tx.translation(),
+                    was_demanded: !is_rotten && unmet_demands.contains(&floating.box_type),
+                    was_rotten: is_rotten,
+                    was_wet: true,
+                });
+
+                if !is_rotten {
+                    ship_hold.crates.push(floating.box_type);
+                }
+
+                commands.entity(crate_entity).despawn_recursive();
+            }
+        }
+    }
+}
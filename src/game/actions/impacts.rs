@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use heron::{CollisionEvent, Velocity};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    game::components::{
+        AnimateWithSpeed, BoxType, HardSurface, LandingFromFactory, PhysicsCrate, PreviousVelocity,
+        WorldEntity,
+    },
+    loader::TextureAssets,
+    GRID_SIZE,
+};
+
+const SHARD_COUNT: usize = 6;
+const SHARD_SPEED: f32 = 80.0;
+const SHARD_SCALE: f32 = 0.25;
+
+/// Event triggered when a fragile crate hits something too hard and breaks
+pub struct OnCrateShattered {
+    pub box_type: BoxType,
+    pub location: Vec3,
+}
+
+/// Remembers how fast each crate was moving before the physics step, so the speed of an
+/// impact can be measured after the collision has already slowed the crate down
+pub fn track_crate_velocity(mut crates: Query<(&Velocity, &mut PreviousVelocity)>) {
+    for (velocity, mut previous) in crates.iter_mut() {
+        previous.0 = velocity.linear;
+    }
+}
+
+/// Breaks fragile crates that hit a hard surface or another crate faster than they can take.
+/// Crates dropping out of the factory land safely, along with anything they land on.
+pub fn shatter_fragile_crates(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut shatter_events: EventWriter<OnCrateShattered>,
+    crates: Query<(&PhysicsCrate, &PreviousVelocity, &Transform)>,
+    landing_from_factory: Query<(), With<LandingFromFactory>>,
+    hard_surfaces: Query<(), With<HardSurface>>,
+) {
+    let mut shattered = vec![];
+
+    for event in collision_events.iter().filter(|evt| evt.is_started()) {
+        let (a, b) = event.rigid_body_entities();
+
+        // only landing on something solid counts, not passing through a sensor
+        let mut is_factory_landing = false;
+        for (this, other) in [(a, b), (b, a)] {
+            let is_solid = crates.get(other).is_ok() || hard_surfaces.get(other).is_ok();
+            if is_solid && landing_from_factory.get(this).is_ok() {
+                is_factory_landing = true;
+                commands.entity(this).remove::<LandingFromFactory>();
+            }
+        }
+
+        if is_factory_landing {
+            continue;
+        }
+
+        for (this, other) in [(a, b), (b, a)] {
+            let (physics_crate, velocity, tx) = match crates.get(this) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            let threshold = match physics_crate.box_type.fragility() {
+                Some(threshold) => threshold,
+                None => continue,
+            };
+
+            // crates hitting each other only feel the difference in their speeds
+            let impact_speed = if let Ok((_, other_velocity, _)) = crates.get(other) {
+                (velocity.0 - other_velocity.0).length()
+            } else if hard_surfaces.get(other).is_ok() {
+                velocity.0.length()
+            } else {
+                continue;
+            };
+
+            if impact_speed < threshold || shattered.contains(&this) {
+                continue;
+            }
+
+            info!(
+                "{:?} shattered on impact at {:.0}",
+                physics_crate.box_type, impact_speed
+            );
+
+            shattered.push(this);
+            commands.entity(this).despawn_recursive();
+            shatter_events.send(OnCrateShattered {
+                box_type: physics_crate.box_type,
+                location: tx.translation,
+            });
+        }
+    }
+}
+
+/// Scatters small pieces of a crate when it shatters
+pub fn spawn_shards(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    mut shatter_events: EventReader<OnCrateShattered>,
+) {
+    let mut rng = thread_rng();
+
+    for evt in shatter_events.iter() {
+        for _ in 0..SHARD_COUNT {
+            let offset = Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-0.25..1.0)) * GRID_SIZE;
+
+            commands
+                .spawn_bundle(SpriteBundle {
+                    texture: evt.box_type.get_image(&textures),
+                    transform: Transform::from_translation(evt.location)
+                        .with_rotation(Quat::from_rotation_z(
+                            rng.gen_range(0.0..std::f32::consts::TAU),
+                        ))
+                        .with_scale(Vec3::splat(SHARD_SCALE)),
+                    ..default()
+                })
+                .insert(AnimateWithSpeed {
+                    speed: SHARD_SPEED,
+                    target: vec![evt.location + offset.extend(0.0)],
+                })
+                .insert(WorldEntity);
+        }
+    }
+}
@@ -1,24 +1,28 @@
 use bevy::prelude::*;
-use iyes_loopless::prelude::IntoConditionalSystem;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionHelpers, IntoConditionalSystem};
 
-use crate::{GameState, GRID_SIZE};
-
-use self::{
-    dragging::{DraggingBox, OnStartDragging},
-    dropping::OnDropCrate,
+use crate::{
+    game::{clock::is_not_paused, conveyor::is_not_building_conveyors},
+    GameState,
 };
 
+pub use self::dragging::DraggingBox;
+use self::{dragging::OnStartDragging, dropping::OnDropCrate};
+
 mod detect_crate_drop_on_target;
 pub use detect_crate_drop_on_target::OnCrateSplashedInWater;
 mod dragging;
 mod dropping;
+mod floating;
+pub use floating::float_crate;
+mod impacts;
 pub use dropping::OnDropCrateOnShip;
+pub use impacts::OnCrateShattered;
+mod picking;
+pub use picking::PointerTarget;
 mod pickup;
-
-// Numbers that are the range cart boxes can fall into
-pub const CART_MIN_Y: f32 = -2.5 * GRID_SIZE + 24.0; // magic numbers
-pub const CART_MAX_Y: f32 = CART_MIN_Y + 28.;
-const CART_SPRITE_HALF_WIDTH: f32 = 80.0;
+mod trajectory;
+pub use trajectory::ThrowAssist;
 
 pub struct ActionPlugin;
 
@@ -27,13 +31,30 @@ impl Plugin for ActionPlugin {
         app.insert_resource(DraggingBox {
             box_entity: None,
             box_type: None,
-            is_front_slot: true,
+            spawned_at: 0.0,
+            is_wet: false,
         })
+        .insert_resource(ThrowAssist::default())
+        .insert_resource(PointerTarget::default())
         .add_event::<OnStartDragging>()
         .add_event::<OnDropCrate>()
         .add_event::<OnDropCrateOnShip>()
         .add_event::<OnCrateSplashedInWater>()
-        .add_system(pickup::click_to_pickup.run_in_state(GameState::Playing))
+        .add_event::<OnCrateShattered>()
+        .add_enter_system(GameState::Playing, picking::spawn_pick_highlight)
+        .add_system(
+            picking::update_pointer_target
+                .run_in_state(GameState::Playing)
+                .label("update_pointer_target"),
+        )
+        .add_system(picking::highlight_pointer_target.run_in_state(GameState::Playing))
+        .add_system(
+            pickup::click_to_pickup
+                .run_in_state(GameState::Playing)
+                .run_if(is_not_building_conveyors)
+                .run_if(is_not_paused)
+                .after("update_pointer_target"),
+        )
         .add_system(dragging::start_dragging.run_in_state(GameState::Playing))
         .add_system(dragging::mouse_follower.run_in_state(GameState::Playing))
         .add_system(dropping::handle_drop.run_in_state(GameState::Playing))
@@ -43,6 +64,22 @@ impl Plugin for ActionPlugin {
         )
         .add_system(
             detect_crate_drop_on_target::detect_crate_drop_on_ship.run_in_state(GameState::Playing),
-        );
+        )
+        .add_system(
+            impacts::shatter_fragile_crates
+                .run_in_state(GameState::Playing)
+                .before("track_crate_velocity"),
+        )
+        .add_system(
+            impacts::track_crate_velocity
+                .run_in_state(GameState::Playing)
+                .label("track_crate_velocity"),
+        )
+        .add_system(impacts::spawn_shards.run_in_state(GameState::Playing))
+        .add_system(floating::float_crates.run_in_state(GameState::Playing))
+        .add_system(floating::catch_floating_crates.run_in_state(GameState::Playing))
+        .add_enter_system(GameState::Playing, trajectory::spawn_trajectory_preview)
+        .add_system(trajectory::toggle_throw_assist.run_in_state(GameState::Playing))
+        .add_system(trajectory::update_trajectory_preview.run_in_state(GameState::Playing));
     }
 }
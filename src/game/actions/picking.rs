@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+use heron::CollisionShape;
+
+use crate::{
+    game::{
+        components::{PickArea, PickHighlight, Pickable, WorldEntity},
+        conveyor::ConveyorBuildMode,
+    },
+    input::MousePosition,
+};
+
+use super::DraggingBox;
+
+/// How far the hover highlight extends past the edge of the hovered entity
+const PICK_HIGHLIGHT_PADDING: f32 = 2.0;
+const PICK_HIGHLIGHT_COLOUR: Color = Color::rgba(1.0, 1.0, 0.8, 0.35);
+
+/// A resource holding the topmost pickable entity under the mouse cursor, if any
+#[derive(Debug, Default)]
+pub struct PointerTarget {
+    pub entity: Option<Entity>,
+}
+
+/// Returns the half extents of the area that can be clicked on a pickable entity in world
+/// units, or None if the entity has no area that can be hit tested
+fn pick_half_extents(
+    scale: Vec3,
+    shape: Option<&CollisionShape>,
+    area: Option<&PickArea>,
+) -> Option<Vec2> {
+    match (shape, area) {
+        // heron ignores the transform scale, so colliders are already in world units
+        (
+            Some(CollisionShape::Cuboid {
+                half_extends,
+                border_radius,
+            }),
+            _,
+        ) => Some(half_extends.truncate() + Vec2::splat(border_radius.unwrap_or(0.0))),
+        (Some(CollisionShape::Sphere { radius }), _) => Some(Vec2::splat(*radius)),
+        (_, Some(area)) => Some(area.0 * scale.truncate()),
+        _ => None,
+    }
+}
+
+pub fn spawn_pick_highlight(mut commands: Commands) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: PICK_HIGHLIGHT_COLOUR,
+                ..default()
+            },
+            visibility: Visibility { is_visible: false },
+            ..default()
+        })
+        .insert(PickHighlight)
+        .insert(WorldEntity);
+}
+
+/// Finds the pickable entity under the mouse cursor, testing the cursor against each entity's
+/// rotated hit area and preferring the entity drawn on top
+#[allow(clippy::type_complexity)]
+pub fn update_pointer_target(
+    mouse_pos: Res<MousePosition>,
+    mut pointer_target: ResMut<PointerTarget>,
+    pickables: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            Option<&CollisionShape>,
+            Option<&PickArea>,
+        ),
+        With<Pickable>,
+    >,
+) {
+    let mut target = None;
+
+    if mouse_pos.in_screen {
+        let mut top_z = f32::NEG_INFINITY;
+
+        for (entity, tx, shape, area) in pickables.iter() {
+            let (scale, rotation, translation) = tx.to_scale_rotation_translation();
+
+            let half_extents = match pick_half_extents(scale, shape, area) {
+                Some(half_extents) => half_extents,
+                None => continue,
+            };
+
+            // rotate the cursor into the entity's frame so rotated crates are hit properly
+            let local = rotation.inverse() * (mouse_pos.world.extend(translation.z) - translation);
+            let is_hit = match shape {
+                Some(CollisionShape::Sphere { radius }) => local.truncate().length() <= *radius,
+                _ => local.x.abs() <= half_extents.x && local.y.abs() <= half_extents.y,
+            };
+
+            if is_hit && translation.z > top_z {
+                top_z = translation.z;
+                target = Some(entity);
+            }
+        }
+    }
+
+    if pointer_target.entity != target {
+        pointer_target.entity = target;
+    }
+}
+
+/// Highlights whatever is under the mouse cursor, unless the player is already carrying a crate
+/// or building conveyors
+#[allow(clippy::type_complexity)]
+pub fn highlight_pointer_target(
+    pointer_target: Res<PointerTarget>,
+    dragging: Res<DraggingBox>,
+    build_mode: Res<ConveyorBuildMode>,
+    targets: Query<(&GlobalTransform, Option<&CollisionShape>, Option<&PickArea>)>,
+    mut highlights: Query<(&mut Transform, &mut Visibility, &mut Sprite), With<PickHighlight>>,
+) {
+    let hovered = pointer_target
+        .entity
+        .filter(|_| dragging.box_entity.is_none() && !build_mode.active)
+        .and_then(|entity| targets.get(entity).ok());
+
+    for (mut tx, mut visibility, mut sprite) in highlights.iter_mut() {
+        let (target_tx, shape, area) = match hovered {
+            Some(hovered) => hovered,
+            None => {
+                visibility.is_visible = false;
+                continue;
+            }
+        };
+
+        let (scale, rotation, translation) = target_tx.to_scale_rotation_translation();
+        let half_extents = match pick_half_extents(scale, shape, area) {
+            Some(half_extents) => half_extents,
+            None => {
+                visibility.is_visible = false;
+                continue;
+            }
+        };
+
+        // sit just in front of the hovered entity, as crates on carts are drawn at the cart's depth
+        tx.translation = translation + 0.001 * Vec3::Z;
+        tx.rotation = rotation;
+        sprite.custom_size = Some(2.0 * half_extents + Vec2::splat(2.0 * PICK_HIGHLIGHT_PADDING));
+        visibility.is_visible = true;
+    }
+}
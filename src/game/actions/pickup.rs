@@ -3,87 +3,79 @@ use leafwing_input_manager::prelude::ActionState;
 
 use crate::{
     game::{
-        actions::{dragging::StartDraggingItem, CART_SPRITE_HALF_WIDTH},
-        components::{Cart, PhysicsCrate},
+        actions::dragging::StartDraggingItem,
+        components::{Cart, CartCrate, FloatingCrate, LeavingCart, PhysicsCrate},
     },
-    input::{MousePosition, PlayerActions},
-    GRID_SIZE,
+    input::PlayerActions,
 };
 
 use super::{
     dragging::{DraggingBox, OnStartDragging},
     dropping::OnDropCrate,
-    CART_MAX_Y, CART_MIN_Y,
+    picking::PointerTarget,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn click_to_pickup(
-    mouse_pos: Res<MousePosition>,
+    pointer_target: Res<PointerTarget>,
     dragging: Res<DraggingBox>,
     mut start_events: EventWriter<OnStartDragging>,
     mut drop_events: EventWriter<OnDropCrate>,
     action_state_query: Query<&ActionState<PlayerActions>>,
-    carts: Query<(Entity, &Transform), With<Cart>>,
-    boxes: Query<(Entity, &Transform), With<PhysicsCrate>>,
+    cart_crates: Query<(&CartCrate, &Parent)>,
+    carts: Query<(&Transform, &Cart), Without<LeavingCart>>,
+    boxes: Query<&Transform, With<PhysicsCrate>>,
+    floating_crates: Query<&FloatingCrate>,
 ) {
     let action_state = action_state_query.single();
-    let x = mouse_pos.world.x;
-    let y = mouse_pos.world.y;
 
     if action_state.just_pressed(PlayerActions::Click) {
-        for (cart_ent, cart_tx) in carts.iter() {
-            let delta = x - cart_tx.translation.x;
-            // carts are 160px wide, the last two grid squares (32px) are for boxes.
-            // be a bit flexible with the clicking (i.e. doen't require directly on the sprite)
-            if !((CART_SPRITE_HALF_WIDTH - 2.0 * GRID_SIZE)..=CART_SPRITE_HALF_WIDTH)
-                .contains(&delta)
-                || !(CART_MIN_Y..=CART_MAX_Y).contains(&y)
-            {
-                continue;
-            }
+        let target = match pointer_target.entity {
+            Some(target) => target,
+            None => return,
+        };
 
-            let zone =
-                (((x - cart_tx.translation.x) - (GRID_SIZE / 2.0)) / GRID_SIZE).floor() as u32;
+        // crates on carts are picked up from the cart, using the slot they sit in
+        if let Ok((cart_crate, parent)) = cart_crates.get(target) {
+            let (cart_tx, cart) = match carts.get(parent.get()) {
+                Ok(cart) => cart,
+                Err(_) => return,
+            };
 
             info!(
-                "Picking up box {} on cart {:?} at {} in the {} zone",
-                zone,
-                cart_ent,
+                "Picking up box {} on {:?} {:?} at {}",
+                cart_crate.slot,
+                cart.cart_type,
+                parent.get(),
                 cart_tx.translation,
-                if zone == 0 { "front" } else { "back" }
             );
 
             // trigger the event to handle drag start
             start_events.send(OnStartDragging {
-                dragged_entity: StartDraggingItem::Cart(cart_ent),
-                is_front_slot: zone == 0,
+                dragged_entity: StartDraggingItem::Cart(parent.get()),
+                slot: cart_crate.slot,
             });
+        } else if let Ok(box_tx) = boxes.get(target) {
+            info!(
+                "Picking up physics crate {:?} from the warehouse at {}",
+                target, box_tx.translation
+            );
 
-            return;
-        }
-
-        // go through the physics boxes and see if we should pick one up
-        // this test is a bit simpler because we can just check x/y bounds
-        for (box_ent, box_tx) in boxes.iter() {
-            let in_x = ((box_tx.translation.x - GRID_SIZE / 2.0)
-                ..(box_tx.translation.x + GRID_SIZE / 2.0))
-                .contains(&x);
-            let in_y = ((box_tx.translation.y - GRID_SIZE / 2.0)
-                ..(box_tx.translation.y + GRID_SIZE / 2.0))
-                .contains(&y);
-
-            if in_x && in_y {
-                info!(
-                    "Picking up physics crate {:?} from the warehouse at {}",
-                    box_ent, box_tx.translation
-                );
+            // trigger the event to handle drag start
+            start_events.send(OnStartDragging {
+                dragged_entity: StartDraggingItem::PhysicsCrate(target),
+                slot: 0,
+            });
+        } else if let Ok(floating) = floating_crates.get(target) {
+            info!(
+                "Fishing floating {:?} {:?} out of the water",
+                floating.box_type, target
+            );
 
-                // trigger the event to handle drag start
-                start_events.send(OnStartDragging {
-                    dragged_entity: StartDraggingItem::PhysicsCrate(box_ent),
-                    is_front_slot: true,
-                });
-                return;
-            }
+            start_events.send(OnStartDragging {
+                dragged_entity: StartDraggingItem::FloatingCrate(target),
+                slot: 0,
+            });
         }
     } else if dragging.box_entity.is_some() && action_state.just_released(PlayerActions::Click) {
         drop_events.send(OnDropCrate);
@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use heron::{CollisionShape, Gravity};
+use leafwing_input_manager::prelude::ActionState;
+
+use crate::{
+    game::{
+        components::{
+            FactoryInput, FollowMouse, HardSurface, SplashCatcher, TrajectoryDot, TrajectoryTarget,
+            WarehouseFloor, Wave, WorldEntity,
+        },
+        wind::Wind,
+    },
+    input::{MousePosition, PlayerActions},
+};
+
+use super::dropping::CRATE_DROP_VELOCITY_FACTOR;
+
+const TRAJECTORY_DOTS: usize = 16;
+const TRAJECTORY_DOT_SIZE: f32 = 4.0;
+const TRAJECTORY_DOT_COLOUR: Color = Color::rgba(1.0, 1.0, 1.0, 0.8);
+
+/// The simulation time step used to predict where a thrown crate lands
+const TRAJECTORY_STEP_SECONDS: f32 = 0.02;
+
+/// The longest flight that is predicted, in seconds
+const TRAJECTORY_MAX_SECONDS: f32 = 3.0;
+
+const TRAJECTORY_Z_POS: f32 = 6.5;
+
+/// A resource holding whether the throw trajectory preview is shown while dragging crates
+pub struct ThrowAssist {
+    pub enabled: bool,
+}
+
+impl Default for ThrowAssist {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// The kinds of thing a thrown crate can land on, each highlighted in a different colour
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ThrowTarget {
+    Ship,
+    Factory,
+    Warehouse,
+    Surface,
+    Water,
+}
+
+impl ThrowTarget {
+    fn colour(&self) -> Color {
+        match self {
+            ThrowTarget::Ship => Color::rgba(0.2, 0.9, 0.3, 0.4),
+            ThrowTarget::Factory => Color::rgba(0.9, 0.8, 0.2, 0.4),
+            ThrowTarget::Warehouse => Color::rgba(0.3, 0.6, 0.95, 0.4),
+            ThrowTarget::Surface => Color::rgba(0.8, 0.8, 0.8, 0.3),
+            ThrowTarget::Water => Color::rgba(0.9, 0.25, 0.2, 0.4),
+        }
+    }
+}
+
+pub fn spawn_trajectory_preview(mut commands: Commands) {
+    for _ in 0..TRAJECTORY_DOTS {
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: TRAJECTORY_DOT_COLOUR,
+                    custom_size: Some(Vec2::splat(TRAJECTORY_DOT_SIZE)),
+                    ..default()
+                },
+                visibility: Visibility { is_visible: false },
+                ..default()
+            })
+            .insert(TrajectoryDot)
+            .insert(WorldEntity);
+    }
+
+    commands
+        .spawn_bundle(SpriteBundle {
+            visibility: Visibility { is_visible: false },
+            ..default()
+        })
+        .insert(TrajectoryTarget)
+        .insert(WorldEntity);
+}
+
+pub fn toggle_throw_assist(
+    mut assist: ResMut<ThrowAssist>,
+    action_state_query: Query<&ActionState<PlayerActions>>,
+) {
+    if action_state_query
+        .single()
+        .just_pressed(PlayerActions::ToggleThrowAssist)
+    {
+        assist.enabled = !assist.enabled;
+        info!("Throw assist: {}", assist.enabled);
+    }
+}
+
+/// Predicts where the dragged crate would land if it was let go now, drawing a dotted arc
+/// to the landing point and highlighting whatever it would land on
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_trajectory_preview(
+    assist: Res<ThrowAssist>,
+    gravity: Res<Gravity>,
+    wind: Res<Wind>,
+    mouse_pos: Res<MousePosition>,
+    dragged_crates: Query<(), With<FollowMouse>>,
+    targets: Query<(
+        &GlobalTransform,
+        &CollisionShape,
+        Option<&Wave>,
+        Option<&FactoryInput>,
+        Option<&SplashCatcher>,
+        Option<&HardSurface>,
+        Option<&WarehouseFloor>,
+    )>,
+    mut dots: Query<
+        (&mut Transform, &mut Visibility, &mut Sprite),
+        (With<TrajectoryDot>, Without<TrajectoryTarget>),
+    >,
+    mut highlights: Query<
+        (&mut Transform, &mut Visibility, &mut Sprite),
+        (With<TrajectoryTarget>, Without<TrajectoryDot>),
+    >,
+) {
+    let is_visible = assist.enabled && mouse_pos.in_screen && !dragged_crates.is_empty();
+
+    if !is_visible {
+        for (_, mut visibility, _) in dots.iter_mut() {
+            visibility.is_visible = false;
+        }
+        for (_, mut visibility, _) in highlights.iter_mut() {
+            visibility.is_visible = false;
+        }
+        return;
+    }
+
+    // step the same motion the physics engine will apply once the crate is released
+    let acceleration = gravity.vector() + wind.crate_acceleration();
+    let mut position = mouse_pos.world.extend(0.0);
+    let mut velocity = (mouse_pos.velocity * CRATE_DROP_VELOCITY_FACTOR).extend(0.0);
+    let mut path = vec![position];
+    let mut hit = None;
+
+    let mut elapsed = 0.0;
+    while elapsed < TRAJECTORY_MAX_SECONDS && hit.is_none() {
+        velocity += acceleration * TRAJECTORY_STEP_SECONDS;
+        position += velocity * TRAJECTORY_STEP_SECONDS;
+        elapsed += TRAJECTORY_STEP_SECONDS;
+        path.push(position);
+
+        for (tx, shape, wave, factory, splash, surface, warehouse) in targets.iter() {
+            let half_extends = match shape {
+                CollisionShape::Cuboid { half_extends, .. } => half_extends.truncate(),
+                _ => continue,
+            };
+
+            let offset = (position.truncate() - tx.translation().truncate()).abs();
+            if offset.x > half_extends.x || offset.y > half_extends.y {
+                continue;
+            }
+
+            let target = if wave.is_some() {
+                ThrowTarget::Ship
+            } else if factory.is_some() {
+                ThrowTarget::Factory
+            } else if splash.is_some() {
+                ThrowTarget::Water
+            } else if warehouse.is_some() {
+                ThrowTarget::Warehouse
+            } else if surface.is_some() {
+                ThrowTarget::Surface
+            } else {
+                continue;
+            };
+
+            hit = Some((target, tx.translation(), half_extends));
+            break;
+        }
+    }
+
+    // spread the dots evenly along the predicted path, tinted to match the target
+    let dot_colour = hit.map_or(TRAJECTORY_DOT_COLOUR, |(target, _, _)| {
+        target.colour().set_a(TRAJECTORY_DOT_COLOUR.a()).as_rgba()
+    });
+    for (idx, (mut tx, mut visibility, mut sprite)) in dots.iter_mut().enumerate() {
+        let path_idx = (idx + 1) * (path.len() - 1) / TRAJECTORY_DOTS;
+        tx.translation = path[path_idx].truncate().extend(TRAJECTORY_Z_POS);
+        sprite.color = dot_colour;
+        visibility.is_visible = true;
+    }
+
+    for (mut tx, mut visibility, mut sprite) in highlights.iter_mut() {
+        match hit {
+            Some((target, location, half_extends)) => {
+                tx.translation = location.truncate().extend(TRAJECTORY_Z_POS);
+                sprite.custom_size = Some(2.0 * half_extends);
+                sprite.color = target.colour();
+                visibility.is_visible = true;
+            }
+            None => visibility.is_visible = false,
+        }
+    }
+}
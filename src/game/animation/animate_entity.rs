@@ -1,9 +1,8 @@
 use bevy::prelude::*;
 
-use crate::game::{
-    components::{AnimateWithSpeed, Cart, Wave},
-    spawners::CartSpawningState,
-};
+use crate::game::clock::GameClock;
+
+use crate::game::components::{AnimateWithSpeed, Cart, LeavingCart, Wave};
 
 pub struct OnShipArrivedAtDestination(pub Entity);
 
@@ -11,8 +10,7 @@ pub struct OnShipArrivedAtDestination(pub Entity);
 #[allow(clippy::type_complexity)]
 pub fn animate_entity(
     mut commands: Commands,
-    time: Res<Time>,
-    mut cart_spawn_state: ResMut<CartSpawningState>,
+    time: Res<GameClock>,
     mut arrival_events: EventWriter<OnShipArrivedAtDestination>,
     mut animated_entities: Query<(
         Entity,
@@ -20,11 +18,12 @@ pub fn animate_entity(
         &mut Transform,
         Option<&Wave>,
         Option<&Cart>,
+        Option<&LeavingCart>,
     )>,
 ) {
     let dt = time.delta_seconds();
 
-    for (ent, mut anim, mut item, wave, cart) in animated_entities.iter_mut() {
+    for (ent, mut anim, mut item, wave, cart, leaving) in animated_entities.iter_mut() {
         let current_waypoint = match anim.target.first() {
             Some(t) => t,
             None => {
@@ -41,9 +40,11 @@ pub fn animate_entity(
                 if wave.is_some() {
                     info!("Despawning a ship {:?}", ent);
                     arrival_events.send(OnShipArrivedAtDestination(ent));
+                } else if cart.is_some() && leaving.is_none() {
+                    info!("Cart {:?} has parked in a loading bay", ent);
+                    commands.entity(ent).remove::<AnimateWithSpeed>();
                 } else if cart.is_some() {
                     info!("Despawning a cart {:?}", ent);
-                    cart_spawn_state.active_carts -= 1;
                     commands.entity(ent).despawn_recursive();
                 } else {
                     info!("Despawning an animated item {:?}", ent);
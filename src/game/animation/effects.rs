@@ -2,14 +2,19 @@ use bevy::prelude::*;
 use rand::{thread_rng, Rng};
 
 use crate::{
-    game::components::{AnimateWithSpeed, VisualEffect},
+    game::{
+        clock::GameClock,
+        components::{AnimateWithSpeed, RainDrop, VisualEffect, WeatherEffect, WorldEntity},
+        weather::{OnWeatherChanged, OnWeatherRestored, Weather},
+        wind::Wind,
+    },
     loader::TextureAssets,
     HEIGHT, WIDTH,
 };
 
 pub fn despawn_visual_effects(
     mut commands: Commands,
-    time: Res<Time>,
+    time: Res<GameClock>,
     effects: Query<(Entity, &VisualEffect)>,
 ) {
     if effects.is_empty() {
@@ -24,7 +29,19 @@ pub fn despawn_visual_effects(
     }
 }
 
+const RAIN_SPEED: f32 = 100.0;
+const SNOW_SPEED: f32 = 60.0;
+const SNOWFLAKE_SIZE: f32 = 3.0;
+
+const FOG_COLOUR: Color = Color::rgba(0.8, 0.8, 0.85, 0.45);
+const HEAT_HAZE_COLOUR: Color = Color::rgba(1.0, 0.6, 0.2, 0.08);
+
 pub fn spawn_rain_effects(mut commands: Commands, textures: Res<TextureAssets>) {
+    spawn_rain(&mut commands, &textures);
+}
+
+/// Not a system - spawns a shower of rain drops over the screen
+fn spawn_rain(commands: &mut Commands, textures: &TextureAssets) {
     let mut rng = thread_rng();
 
     // Probably should spawn these over time but :shrug:
@@ -40,8 +57,122 @@ pub fn spawn_rain_effects(mut commands: Commands, textures: Res<TextureAssets>)
                 ..default()
             })
             .insert(AnimateWithSpeed {
-                speed: 100.,
+                speed: RAIN_SPEED,
                 target: vec![Vec3::new(x, -HEIGHT * 0.6, 8.0)],
-            });
+            })
+            .insert(RainDrop);
+    }
+}
+
+/// Blows falling rain sideways with the wind, tilting the drops to match
+pub fn slant_rain(
+    time: Res<GameClock>,
+    wind: Res<Wind>,
+    mut drops: Query<(&mut Transform, &mut AnimateWithSpeed), With<RainDrop>>,
+) {
+    let drift = wind.velocity * time.delta_seconds();
+    let rotation = Quat::from_rotation_z(wind.velocity.atan2(RAIN_SPEED));
+
+    for (mut tx, mut animation) in drops.iter_mut() {
+        // move the waypoint too, so the drop keeps falling at the same speed
+        tx.translation.x += drift;
+        for target in animation.target.iter_mut() {
+            target.x += drift;
+        }
+
+        tx.rotation = rotation;
+    }
+}
+
+/// Swaps the effects of the old weather for the new one
+pub fn update_weather_effects(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    mut weather_events: EventReader<OnWeatherChanged>,
+    effects: Query<Entity, With<WeatherEffect>>,
+) {
+    for evt in weather_events.iter() {
+        for effect in effects.iter() {
+            commands.entity(effect).despawn_recursive();
+        }
+
+        spawn_weather_effects(&mut commands, &textures, evt.to);
+    }
+}
+
+/// Starts the effects of the weather a saved game was in again, including the rain in a storm
+pub fn resume_weather_effects(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    mut restored_events: EventReader<OnWeatherRestored>,
+) {
+    for evt in restored_events.iter() {
+        if evt.0 == Weather::Stormy {
+            spawn_rain(&mut commands, &textures);
+        }
+
+        spawn_weather_effects(&mut commands, &textures, evt.0);
+    }
+}
+
+/// Not a system - draws fog over the sea, a haze over everything in a heatwave or falling snow
+fn spawn_weather_effects(commands: &mut Commands, textures: &TextureAssets, weather: Weather) {
+    match weather {
+        Weather::Foggy => {
+            commands
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: FOG_COLOUR,
+                        custom_size: Some(Vec2::new(WIDTH, 0.4 * HEIGHT)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, -0.3 * HEIGHT, 7.5),
+                    ..default()
+                })
+                .insert(WeatherEffect)
+                .insert(WorldEntity);
+        }
+        Weather::Heatwave => {
+            commands
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: HEAT_HAZE_COLOUR,
+                        custom_size: Some(Vec2::new(WIDTH, HEIGHT)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 7.5),
+                    ..default()
+                })
+                .insert(WeatherEffect)
+                .insert(WorldEntity);
+        }
+        Weather::Snowy => {
+            let mut rng = thread_rng();
+
+            for _ in 0..150 {
+                let x = rng.gen_range(0.0..WIDTH) - WIDTH / 2.0;
+                let y = rng.gen_range(0.0..2.0 * HEIGHT) + 0.5 * HEIGHT;
+
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        texture: textures.rain_drop.clone(),
+                        sprite: Sprite {
+                            color: Color::WHITE,
+                            custom_size: Some(Vec2::splat(SNOWFLAKE_SIZE)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(x, y, 8.0),
+                        ..default()
+                    })
+                    .insert(AnimateWithSpeed {
+                        speed: SNOW_SPEED,
+                        target: vec![Vec3::new(x, -HEIGHT * 0.6, 8.0)],
+                    })
+                    .insert(RainDrop)
+                    .insert(WeatherEffect)
+                    .insert(WorldEntity);
+            }
+        }
+        Weather::Sunny | Weather::Stormy => {}
     }
 }
@@ -9,10 +9,16 @@ mod effects;
 mod splashes;
 pub use animate_entity::OnShipArrivedAtDestination;
 mod ship_bob;
+mod sinking;
+mod spoilage;
 use iyes_loopless::prelude::IntoConditionalSystem;
 
 use crate::{
-    game::{OnRainStart, SystemLabels},
+    game::{
+        clock::GameClock,
+        weather::{OnWeatherChanged, OnWeatherRestored},
+        OnRainStart, SystemLabels,
+    },
     GameState,
 };
 
@@ -35,7 +41,13 @@ impl Plugin for AnimationPlugin {
             .add_system(ship_bob::ship_bob.run_not_in_state(GameState::Loading))
             .add_system(effects::despawn_visual_effects.run_not_in_state(GameState::Loading))
             .add_system(splashes::splash_when_hitting_water.run_not_in_state(GameState::Loading))
-            .add_system(effects::spawn_rain_effects.run_on_event::<OnRainStart>());
+            .add_system(splashes::splash_when_ship_founders.run_not_in_state(GameState::Loading))
+            .add_system(sinking::animate_sinking_ships.run_in_state(GameState::Playing))
+            .add_system(spoilage::tint_spoiling_crates.run_in_state(GameState::Playing))
+            .add_system(effects::spawn_rain_effects.run_on_event::<OnRainStart>())
+            .add_system(effects::slant_rain.run_in_state(GameState::Playing))
+            .add_system(effects::update_weather_effects.run_on_event::<OnWeatherChanged>())
+            .add_system(effects::resume_weather_effects.run_on_event::<OnWeatherRestored>());
     }
 }
 
@@ -73,7 +85,7 @@ impl AssetLoader for BenimationLoader {
 }
 
 fn update_animation_frames(
-    time: Res<Time>,
+    time: Res<GameClock>,
     animations: Res<Assets<Animation>>,
     mut query: Query<(
         &mut AnimationState,
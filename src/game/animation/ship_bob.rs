@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 
+use crate::game::clock::GameClock;
+
 use crate::game::components::{Ship, ShipHold};
 
 const BOB_AMOUNT: f32 = 4.;
 const ROTATION_FACTOR: f32 = 0.02;
 
-pub fn ship_bob(time: Res<Time>, mut ships: Query<(&mut Transform, &Ship, &ShipHold)>) {
+pub fn ship_bob(time: Res<GameClock>, mut ships: Query<(&mut Transform, &Ship, &ShipHold)>) {
     let t = time.time_since_startup().as_secs_f32();
     let dt = time.delta_seconds();
 
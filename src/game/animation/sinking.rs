@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::clock::GameClock,
+    game::components::{Ship, Sinking},
+    GRID_SIZE,
+};
+
+const SINK_SPEED: f32 = 1.5 * GRID_SIZE;
+const SINK_ROLL_SPEED: f32 = 0.15;
+
+/// Slowly rolls foundering ships over and drags them below the waves, despawning them
+/// once they have fully sunk
+pub fn animate_sinking_ships(
+    mut commands: Commands,
+    time: Res<GameClock>,
+    mut sinking_waves: Query<(Entity, &mut Sinking, &mut Sprite, &Children)>,
+    mut ships: Query<(&mut Ship, &mut Transform, &mut TextureAtlasSprite)>,
+) {
+    let dt = time.delta_seconds();
+
+    for (wave_entity, mut sinking, mut wave_sprite, children) in sinking_waves.iter_mut() {
+        sinking.0.tick(time.delta());
+
+        if sinking.0.finished() {
+            info!("Ship {:?} has sunk, despawning", wave_entity);
+            commands.entity(wave_entity).despawn_recursive();
+            continue;
+        }
+
+        let alpha = 1.0 - sinking.0.percent();
+        wave_sprite.color.set_a(alpha);
+
+        for child in children.iter() {
+            if let Ok((mut ship, mut tx, mut sprite)) = ships.get_mut(*child) {
+                ship.y_offset -= dt * SINK_SPEED;
+                tx.rotate_z(dt * SINK_ROLL_SPEED);
+                sprite.color.set_a(alpha);
+            }
+        }
+    }
+}
@@ -1,16 +1,21 @@
 use bevy::prelude::*;
 
 use crate::{
-    game::{actions::OnCrateSplashedInWater, components::VisualEffect},
+    game::{
+        actions::OnCrateSplashedInWater, clock::GameClock, components::VisualEffect,
+        spawners::OnShipFoundered,
+    },
     loader::{AnimationAssets, TextureAssets},
     GRID_SIZE,
 };
 
 use super::AnimationState;
 
+const FOUNDERING_SPLASH_COUNT: usize = 5;
+
 pub fn splash_when_hitting_water(
     mut commands: Commands,
-    time: Res<Time>,
+    time: Res<GameClock>,
     textures: Res<TextureAssets>,
     animations: Res<AnimationAssets>,
     mut splash_event: EventReader<OnCrateSplashedInWater>,
@@ -29,3 +34,31 @@ pub fn splash_when_hitting_water(
             .insert(animations.splashes.clone());
     }
 }
+
+pub fn splash_when_ship_founders(
+    mut commands: Commands,
+    time: Res<GameClock>,
+    textures: Res<TextureAssets>,
+    animations: Res<AnimationAssets>,
+    mut founder_events: EventReader<OnShipFoundered>,
+) {
+    for evt in founder_events.iter() {
+        // spread a few big splashes along the hull
+        for idx in 0..FOUNDERING_SPLASH_COUNT {
+            let offset = (idx as f32 - (FOUNDERING_SPLASH_COUNT / 2) as f32) * 1.5 * GRID_SIZE;
+
+            commands
+                .spawn_bundle(SpriteSheetBundle {
+                    texture_atlas: textures.splashes.clone(),
+                    transform: Transform::from_translation(
+                        evt.location + Vec3::new(offset, 0.0, 0.5),
+                    )
+                    .with_scale(Vec3::splat(2.0)),
+                    ..default()
+                })
+                .insert(VisualEffect(time.time_since_startup().as_secs_f32() + 3.0))
+                .insert(AnimationState::default())
+                .insert(animations.splashes.clone());
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    actions::DraggingBox,
+    clock::GameClock,
+    components::{FollowMouse, PhysicsCrate, WetCrate},
+};
+
+/// Darkens and blues a crate's tint once it has been soaked in the water
+fn wet_tint(tint: Color) -> Color {
+    Color::rgba(tint.r() * 0.7, tint.g() * 0.8, tint.b(), tint.a())
+}
+
+/// The tint for a physics crate, given its age and whether it has been in the water
+fn crate_tint(physics_crate: &PhysicsCrate, now: f64, is_wet: bool) -> Color {
+    let tint = physics_crate
+        .box_type
+        .spoilage_tint(now - physics_crate.spawned_at);
+
+    if is_wet {
+        wet_tint(tint)
+    } else {
+        tint
+    }
+}
+
+/// Tints perishable crates as they age, so rotting crates are easy to spot, and tints
+/// crates fished out of the water. Crates from the factory and ships are plain sprites, while
+/// crates thrown from carts or fished out of the water keep their texture atlas sprite.
+#[allow(clippy::type_complexity)]
+pub fn tint_spoiling_crates(
+    time: Res<GameClock>,
+    dragging: Res<DraggingBox>,
+    mut crates: Query<(&PhysicsCrate, &mut Sprite, Option<&WetCrate>)>,
+    mut atlas_crates: Query<
+        (&PhysicsCrate, &mut TextureAtlasSprite, Option<&WetCrate>),
+        Without<FollowMouse>,
+    >,
+    mut dragged_crates: Query<&mut TextureAtlasSprite, With<FollowMouse>>,
+) {
+    let now = time.seconds_since_startup();
+
+    for (physics_crate, mut sprite, wet) in crates.iter_mut() {
+        let tint = crate_tint(physics_crate, now, wet.is_some());
+        if sprite.color != tint {
+            sprite.color = tint;
+        }
+    }
+
+    for (physics_crate, mut sprite, wet) in atlas_crates.iter_mut() {
+        let tint = crate_tint(physics_crate, now, wet.is_some());
+        if sprite.color != tint {
+            sprite.color = tint;
+        }
+    }
+
+    if let Some(box_type) = dragging.box_type {
+        let mut tint = box_type.spoilage_tint(now - dragging.spawned_at);
+        if dragging.is_wet {
+            tint = wet_tint(tint);
+        }
+
+        for mut sprite in dragged_crates.iter_mut() {
+            sprite.color = tint;
+        }
+    }
+}
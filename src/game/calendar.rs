@@ -0,0 +1,206 @@
+use bevy::{
+    asset::{AssetLoader, BoxedFuture, Error, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+};
+use chrono::{Duration, NaiveDate};
+use iyes_loopless::prelude::{AppLooplessStateExt, IntoConditionalSystem};
+use serde::Deserialize;
+
+use crate::{
+    game::{
+        components::BoxType,
+        day_night_cycle::{OnNewDay, TimeOfDay},
+    },
+    loader::CalendarAssets,
+    GameState,
+};
+
+/// What a calendar event does to trade at the docks while it is running
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum CalendarEffect {
+    /// Each ship scheduled during the event has the given chance of demanding the good
+    Demand { good: BoxType, chance: f64 },
+
+    /// No ships berth while the event is running
+    PortClosed,
+
+    /// The sky colour is multiplied by the tint
+    DarkSkies { tint: [f32; 3] },
+}
+
+/// A dated event that changes trade for a number of days, read from a calendar file
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct CalendarEvent {
+    #[serde(deserialize_with = "deserialize_date")]
+    pub date: NaiveDate,
+    pub days: i64,
+    pub title: String,
+    pub description: String,
+    pub effect: CalendarEffect,
+}
+
+impl CalendarEvent {
+    /// Whether the event is running on the given date
+    pub fn is_running(&self, date: NaiveDate) -> bool {
+        date >= self.date && date < self.date + Duration::days(self.days)
+    }
+}
+
+fn deserialize_date<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    NaiveDate::parse_from_str(&value, "%Y-%m-%d").map_err(serde::de::Error::custom)
+}
+
+/// A calendar asset, holding every dated event in a `.calendar.yml` file
+#[derive(TypeUuid, Deref)]
+#[uuid = "3c1f1a52-8d0e-4b6f-9a7d-52e0c4b8f2a1"]
+pub struct CalendarEvents(Vec<CalendarEvent>);
+
+#[derive(Default)]
+struct CalendarLoader;
+
+impl AssetLoader for CalendarLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, anyhow::Result<(), Error>> {
+        Box::pin(async move {
+            let events = CalendarEvents(serde_yaml::from_slice(bytes)?);
+            load_context.set_default_asset(LoadedAsset::new(events));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["calendar.yml"]
+    }
+}
+
+/// Event triggered when a calendar event starts, so it can be announced
+pub struct OnCalendarEventStarted(pub CalendarEvent);
+
+/// Event triggered when a saved game is restored, so the calendar can catch up with the
+/// restored date without it counting as a new day
+pub struct OnCalendarRestored(pub NaiveDate);
+
+/// A resource holding the calendar events running today
+#[derive(Default)]
+pub struct Calendar {
+    pub active: Vec<CalendarEvent>,
+}
+
+impl Calendar {
+    /// Replaces the running events with those running on the given date, returning the
+    /// events that weren't already running
+    fn update(&mut self, events: &[CalendarEvent], date: NaiveDate) -> Vec<CalendarEvent> {
+        let running = events
+            .iter()
+            .filter(|evt| evt.is_running(date))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let started = running
+            .iter()
+            .filter(|evt| !self.active.contains(evt))
+            .cloned()
+            .collect();
+
+        self.active = running;
+        started
+    }
+
+    /// Whether the port is closed to ships today
+    pub fn is_port_closed(&self) -> bool {
+        self.active
+            .iter()
+            .any(|evt| evt.effect == CalendarEffect::PortClosed)
+    }
+
+    /// The goods ships are keen on today, with the chance each ship demands them
+    pub fn extra_demands(&self) -> Vec<(BoxType, f64)> {
+        self.active
+            .iter()
+            .filter_map(|evt| match evt.effect {
+                CalendarEffect::Demand { good, chance } => Some((good, chance)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The tint applied to the sky by today's events
+    pub fn sky_tint(&self) -> Vec3 {
+        self.active
+            .iter()
+            .fold(Vec3::ONE, |tint, evt| match evt.effect {
+                CalendarEffect::DarkSkies { tint: event_tint } => tint * Vec3::from(event_tint),
+                _ => tint,
+            })
+    }
+}
+
+pub struct CalendarPlugin;
+
+impl Plugin for CalendarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<CalendarEvents>()
+            .init_asset_loader::<CalendarLoader>()
+            .init_resource::<Calendar>()
+            .add_event::<OnCalendarEventStarted>()
+            .add_event::<OnCalendarRestored>()
+            .add_enter_system(GameState::Playing, reset_calendar)
+            .add_system(update_calendar.run_in_state(GameState::Playing));
+    }
+}
+
+fn reset_calendar(
+    time_of_day: Res<TimeOfDay>,
+    calendar_assets: Res<CalendarAssets>,
+    calendar_events: Res<Assets<CalendarEvents>>,
+    mut calendar: ResMut<Calendar>,
+    mut started_events: EventWriter<OnCalendarEventStarted>,
+) {
+    calendar.active.clear();
+
+    if let Some(events) = calendar_events.get(&calendar_assets.events_1883) {
+        for started in calendar.update(events, time_of_day.today) {
+            started_events.send(OnCalendarEventStarted(started));
+        }
+    }
+}
+
+/// Starts and ends calendar events as the date rolls over at midnight, or jumps to the date
+/// of a restored game
+fn update_calendar(
+    mut new_days: EventReader<OnNewDay>,
+    mut restored: EventReader<OnCalendarRestored>,
+    calendar_assets: Res<CalendarAssets>,
+    calendar_events: Res<Assets<CalendarEvents>>,
+    mut calendar: ResMut<Calendar>,
+    mut started_events: EventWriter<OnCalendarEventStarted>,
+) {
+    let dates = new_days
+        .iter()
+        .map(|new_day| new_day.0)
+        .chain(restored.iter().map(|restored| restored.0))
+        .collect::<Vec<_>>();
+    if dates.is_empty() {
+        return;
+    }
+
+    let events = match calendar_events.get(&calendar_assets.events_1883) {
+        Some(events) => events,
+        None => return,
+    };
+
+    for date in dates {
+        for started in calendar.update(events, date) {
+            info!("Calendar event started: {}", started.title);
+            started_events.send(OnCalendarEventStarted(started));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use heron::PhysicsTime;
+use iyes_loopless::prelude::{AppLooplessStateExt, IntoConditionalSystem};
+use leafwing_input_manager::prelude::ActionState;
+
+use crate::{input::PlayerActions, GameState};
+
+/// The speeds the game can be played at
+pub const CLOCK_SPEEDS: [u32; 3] = [1, 2, 4];
+
+/// A resource holding the time in the game world, which can be paused and sped up.
+/// Gameplay systems should read this rather than `Time`, which always runs in real time.
+#[derive(Debug)]
+pub struct GameClock {
+    speed: u32,
+    paused: bool,
+    delta: Duration,
+    elapsed: Duration,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            speed: 1,
+            paused: false,
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl GameClock {
+    /// Moves the game clock on by the given amount of real time
+    fn tick(&mut self, real_delta: Duration) {
+        self.delta = if self.paused {
+            Duration::ZERO
+        } else {
+            real_delta * self.speed
+        };
+        self.elapsed += self.delta;
+    }
+
+    /// The game time that passed in the last frame
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    pub fn delta_seconds_f64(&self) -> f64 {
+        self.delta.as_secs_f64()
+    }
+
+    /// The game time that has passed since the app started
+    pub fn time_since_startup(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn seconds_since_startup(&self) -> f64 {
+        self.elapsed.as_secs_f64()
+    }
+
+    /// Winds the clock to the time a saved game was played up to, so the times stored in
+    /// the saved game still make sense
+    pub fn set_seconds_since_startup(&mut self, seconds: f64) {
+        self.elapsed = Duration::from_secs_f64(seconds);
+    }
+
+    pub fn speed(&self) -> u32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: u32) {
+        self.speed = speed;
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// How fast the game is running compared to real time, which is zero when paused
+    pub fn scale(&self) -> f32 {
+        if self.paused {
+            0.0
+        } else {
+            self.speed as f32
+        }
+    }
+}
+
+/// Run condition for gameplay input, so the player can't keep playing while the game is paused
+pub fn is_not_paused(clock: Res<GameClock>) -> bool {
+    !clock.is_paused()
+}
+
+pub struct GameClockPlugin;
+
+impl Plugin for GameClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GameClock::default())
+            .add_system_to_stage(CoreStage::PreUpdate, tick_game_clock)
+            .add_enter_system(GameState::Playing, reset_game_clock)
+            .add_system(clock_hotkeys.run_in_state(GameState::Playing))
+            .add_system(sync_physics_time);
+    }
+}
+
+fn tick_game_clock(time: Res<Time>, mut clock: ResMut<GameClock>) {
+    clock.tick(time.delta());
+}
+
+/// Every game starts unpaused at normal speed. The elapsed time carries on, as it is used
+/// to timestamp things across the whole app.
+fn reset_game_clock(mut clock: ResMut<GameClock>) {
+    clock.set_speed(1);
+}
+
+fn clock_hotkeys(
+    mut clock: ResMut<GameClock>,
+    action_state_query: Query<&ActionState<PlayerActions>>,
+) {
+    let action_state = action_state_query.single();
+
+    if action_state.just_pressed(PlayerActions::Pause) {
+        clock.toggle_pause();
+        info!("Game paused: {}", clock.is_paused());
+    }
+
+    let speeds = [
+        (PlayerActions::NormalSpeed, CLOCK_SPEEDS[0]),
+        (PlayerActions::DoubleSpeed, CLOCK_SPEEDS[1]),
+        (PlayerActions::QuadrupleSpeed, CLOCK_SPEEDS[2]),
+    ];
+
+    for (action, speed) in speeds {
+        if action_state.just_pressed(action) {
+            clock.set_speed(speed);
+            info!("Game speed: {}x", speed);
+        }
+    }
+}
+
+/// Runs the physics simulation at the same speed as the game clock
+fn sync_physics_time(clock: Res<GameClock>, mut physics_time: ResMut<PhysicsTime>) {
+    if physics_time.scale() != clock.scale() {
+        physics_time.set_scale(clock.scale());
+    }
+}
@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 use rand::{rngs::ThreadRng, Rng};
+use serde::{Deserialize, Serialize};
 
-use crate::{loader::TextureAssets, GRID_SIZE};
+use crate::{game::spawners::cart_types::CartType, loader::TextureAssets, GRID_SIZE};
 
 #[derive(Component)]
 pub struct Torch;
@@ -10,6 +11,9 @@ pub struct Torch;
 pub struct Ship {
     pub y_offset: f32,
     pub phase: f32,
+
+    /// How far the ship is leaning over in the wind, in radians
+    pub heel: f32,
 }
 
 impl Ship {
@@ -17,10 +21,15 @@ impl Ship {
         Self {
             y_offset: 4.0 * GRID_SIZE,
             phase: rng.gen_range(-3.1..3.1),
+            heel: 0.0,
         }
     }
 }
 
+/// A ship (wave) entity which is sinking beneath the waves, despawned when the timer finishes
+#[derive(Component)]
+pub struct Sinking(pub Timer);
+
 #[derive(Component)]
 pub struct TopUiBar;
 
@@ -65,7 +74,7 @@ pub struct Sun;
 #[derive(Component)]
 pub struct Star;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BoxType {
     // inputs
     Glassware = 0,
@@ -81,31 +90,43 @@ pub enum BoxType {
     Wine = 8,
 }
 
-// Because we can't loop or rand::choose over all enum values, we need
-// to define a list of possible enum values here for randomly demanding
-// crates. Make sure to keep this up to date with the enum above.
-//
-// To change the spawn likelihood, just add an item more often :laugh
-pub const BOX_DEMANDS: [BoxType; 16] = [
-    BoxType::Apples,
-    BoxType::Grapes,
-    BoxType::Honey,
-    BoxType::Wheat,
-    BoxType::Cider,
-    BoxType::Cider,
-    BoxType::Cider,
-    BoxType::Wine,
-    BoxType::Wine,
-    BoxType::Wine,
-    BoxType::Mead,
-    BoxType::Mead,
-    BoxType::Mead,
-    BoxType::Beer,
-    BoxType::Beer,
-    BoxType::Beer,
-];
+/// The tint applied to crates that have completely rotted
+const ROTTEN_TINT: Vec3 = Vec3::new(0.45, 0.5, 0.3);
 
 impl BoxType {
+    /// How long (in seconds) this good stays fresh for, or None if it never spoils
+    pub fn shelf_life(&self) -> Option<f32> {
+        match self {
+            BoxType::Apples => Some(60.0),
+            BoxType::Grapes => Some(50.0),
+            BoxType::Wheat => Some(90.0),
+            _ => None,
+        }
+    }
+
+    /// The impact speed that will shatter a crate of this good, or None if it can't break
+    pub fn fragility(&self) -> Option<f32> {
+        match self {
+            BoxType::Glassware => Some(260.0),
+            BoxType::Wine => Some(300.0),
+            _ => None,
+        }
+    }
+
+    /// How spoiled a crate of this good is after the given age, from 0 (fresh) to 1 (rotten)
+    pub fn spoilage(&self, age: f64) -> f32 {
+        match self.shelf_life() {
+            Some(shelf_life) => (age as f32 / shelf_life).clamp(0.0, 1.0),
+            None => 0.0,
+        }
+    }
+
+    /// The colour to tint a crate of this good after the given age, so it looks more decayed
+    pub fn spoilage_tint(&self, age: f64) -> Color {
+        let tint = Vec3::ONE.lerp(ROTTEN_TINT, self.spoilage(age));
+        Color::rgb(tint.x, tint.y, tint.z)
+    }
+
     pub(crate) fn get_image(&self, textures: &TextureAssets) -> Handle<Image> {
         match self {
             BoxType::Glassware => textures.box_type_glassware.clone(),
@@ -124,22 +145,139 @@ impl BoxType {
 #[derive(Component)]
 pub struct PhysicsCrate {
     pub box_type: BoxType,
+
+    /// The time (since startup) the crate came off a cart, out of the factory or off a ship
+    pub spawned_at: f64,
 }
 
+impl PhysicsCrate {
+    pub fn is_rotten(&self, now: f64) -> bool {
+        self.box_type.spoilage(now - self.spawned_at) >= 1.0
+    }
+}
+
+/// A crate that has been fished out of the water, and is worth less when delivered
+#[derive(Component)]
+pub struct WetCrate;
+
+/// A crate bobbing about in the water, drifting with the wind until it sinks
+#[derive(Component)]
+pub struct FloatingCrate {
+    pub box_type: BoxType,
+    pub spawned_at: f64,
+
+    /// The speed the crate is drifting sideways at, not counting the wind
+    pub drift: f32,
+    pub phase: f32,
+
+    /// Counts down until the crate sinks
+    pub timer: Timer,
+}
+
+/// A conveyor segment that carries crates resting on it along
+#[derive(Component)]
+pub struct ConveyorBelt {
+    /// The sign of the direction crates are carried in (1.0 is to the right)
+    pub direction: f32,
+}
+
+/// One of the dots showing the predicted path of a dragged crate
+#[derive(Component)]
+pub struct TrajectoryDot;
+
+/// Highlights whatever a dragged crate is predicted to land on
+#[derive(Component)]
+pub struct TrajectoryTarget;
+
+/// Shows where a conveyor will be placed in build mode
+#[derive(Component)]
+pub struct ConveyorGhost;
+
+#[derive(Component)]
+pub struct CraneTower;
+
+#[derive(Component)]
+pub struct CraneHook;
+
+/// The crate hanging from the crane hook, hidden when the crane isn't carrying anything
+#[derive(Component)]
+pub struct CraneHookCrate;
+
+#[derive(Component)]
+pub struct CraneButton;
+
+/// The velocity a physics crate had before the last physics step
+#[derive(Component, Default)]
+pub struct PreviousVelocity(pub Vec3);
+
+#[derive(Component)]
+pub struct RainDrop;
+
+/// The picture of the dock behind everything else, tinted with the seasons
+#[derive(Component)]
+pub struct Background;
+
+/// Part of the visual effect for the current weather, removed when the weather changes
+#[derive(Component)]
+pub struct WeatherEffect;
+
+/// A crate that was pushed off a full warehouse, and isn't counted while it falls off
+#[derive(Component)]
+pub struct ToppledCrate;
+
+#[derive(Component)]
+pub struct WarehouseUi;
+
+#[derive(Component)]
+pub struct WarehouseUiTitle;
+
+#[derive(Component)]
+pub struct WarehouseUiRow;
+
 #[derive(Component)]
 pub struct Cart {
-    pub front: Option<BoxType>,
-    pub back: Option<BoxType>,
+    pub cart_type: CartType,
+
+    /// The crate in each cargo slot, `None` once it has been picked up
+    pub slots: Vec<Option<BoxType>>,
 }
 
+/// A cart waiting in a loading bay, which leaves when its patience runs out
+#[derive(Component)]
+pub struct CartPatience {
+    pub bay: usize,
+    pub timer: Timer,
+}
+
+/// A cart which has run out of patience (or crates) and is leaving the dock
+#[derive(Component)]
+pub struct LeavingCart;
+
+#[derive(Component)]
+pub struct CartPatienceMeter;
+
 #[derive(Component)]
 pub struct FollowMouse;
 
 #[derive(Component)]
 pub struct CartCrate {
-    pub is_front_slot: bool,
+    pub slot: usize,
 }
 
+/// An entity the player can pick up by clicking on it. The area that can be clicked is the
+/// entity's `CollisionShape`, or its `PickArea` if it doesn't have a collider
+#[derive(Component)]
+pub struct Pickable;
+
+/// The half extents of the clickable area of a pickable entity without a collider,
+/// before the entity's scale is applied
+#[derive(Component)]
+pub struct PickArea(pub Vec2);
+
+/// Highlights the pickable entity under the mouse cursor
+#[derive(Component)]
+pub struct PickHighlight;
+
 #[derive(Component)]
 pub struct Wave;
 
@@ -149,9 +287,14 @@ pub struct ScoreUi;
 #[derive(Clone, Component, Debug)]
 pub struct SpawnShipRequest {
     pub demands: Vec<BoxType>,
+    pub imports: Vec<BoxType>,
     pub expiry: f32,
 }
 
+/// Goods a ship (wave) entity is bringing in from overseas, unloaded as it passes the warehouse
+#[derive(Component, Clone, Debug)]
+pub struct InboundCargo(pub Vec<BoxType>);
+
 #[derive(Component)]
 pub struct TutorialMarker(pub u8);
 
@@ -183,23 +326,99 @@ pub struct VisualEffect(pub f32);
 #[derive(Component)]
 pub struct CartQueueUi;
 
+/// A button in the cart delivery queue, holding the id of the queued item it shows
+#[derive(Component)]
+pub struct CartQueueUiItem(pub u64);
+
+/// The estimated arrival time text for an item in the cart delivery queue
 #[derive(Component)]
-pub struct CartQueueUiItem;
+pub struct CartQueueUiEta(pub usize);
 
 #[derive(Component)]
 pub struct CartQueueUiButton(pub BoxType);
 
+#[derive(Component)]
+pub struct ShipScheduleUi;
+
+#[derive(Component)]
+pub struct SupplierPanelUi;
+
+#[derive(Component)]
+pub struct SupplierPanelRow;
+
+/// What a button on the supplier panel does to the standing order for a good
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SupplierAction {
+    Place,
+    TogglePause,
+    Cancel,
+}
+
+#[derive(Component)]
+pub struct SupplierOrderButton {
+    pub good: BoxType,
+    pub action: SupplierAction,
+}
+
+#[derive(Component)]
+pub struct ShipScheduleUiRow;
+
 #[derive(Component)]
 pub struct WorldEntity;
 
 #[derive(Component)]
 pub struct MenuItem;
 
+#[derive(Component)]
+pub struct GameModeText;
+
 #[derive(Component)]
 pub struct CurrentDateMarker;
 
+/// The text showing the weather forecast for the next few days
+#[derive(Component)]
+pub struct ForecastUi;
+
+/// The overlay that darkens the docks at night, with the lights it leaves pools of light around
+#[derive(Component, Default)]
+pub struct DarknessOverlay {
+    pub lights: Vec<Vec2>,
+}
+
+/// The night work a button on the night shift panel pays for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NightWork {
+    Carts,
+    FactoryLamps,
+}
+
+#[derive(Component)]
+pub struct NightShiftButton(pub NightWork);
+
+/// A banner announcing a calendar event, removed when the timer finishes
+#[derive(Component)]
+pub struct CalendarBanner {
+    pub timer: Timer,
+}
+
+/// A button that pauses the game clock or sets its speed
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockButton {
+    Pause,
+    Speed(u32),
+}
+
 #[derive(Component)]
 pub struct HardSurface;
 
 #[derive(Component)]
 pub struct HardSurfaceHandled;
+
+/// The floor of the warehouse, which crates are stacked on
+#[derive(Component)]
+pub struct WarehouseFloor;
+
+/// A crate that has just come out of the factory and hasn't landed yet. Its first landing is
+/// part of the factory's own handling, so it can't break a fragile crate.
+#[derive(Component)]
+pub struct LandingFromFactory;
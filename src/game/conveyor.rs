@@ -0,0 +1,301 @@
+use bevy::prelude::*;
+use heron::{CollisionLayers, CollisionShape, Collisions, RigidBody, Velocity};
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionHelpers, IntoConditionalSystem};
+use leafwing_input_manager::prelude::ActionState;
+
+use crate::{
+    game::{
+        actions::DraggingBox,
+        clock::is_not_paused,
+        components::{ConveyorBelt, ConveyorGhost, HardSurface, PhysicsCrate, WorldEntity},
+        spawners::GamePhysicsLayer,
+        ui::Score,
+    },
+    input::{MousePosition, PlayerActions},
+    loader::TextureAssets,
+    GameState, GRID_SIZE,
+};
+
+/// The size of a single conveyor segment
+pub const CONVEYOR_SEGMENT_SIZE: Vec2 = Vec2::new(2.0 * GRID_SIZE, 0.25 * GRID_SIZE);
+
+/// How fast conveyors carry crates along, in pixels per second
+pub const CONVEYOR_SPEED: f32 = 60.0;
+
+/// The cost of buying a single conveyor segment
+pub const CONVEYOR_SEGMENT_COST: f32 = 20.0;
+
+const CONVEYOR_Z_POS: f32 = 0.5;
+const CONVEYOR_COLOUR: Color = Color::rgb(0.35, 0.3, 0.25);
+const CONVEYOR_GHOST_COLOUR: Color = Color::rgba(0.35, 0.3, 0.25, 0.5);
+const CONVEYOR_UNAFFORDABLE_GHOST_COLOUR: Color = Color::rgba(0.8, 0.15, 0.1, 0.5);
+const CONVEYOR_ARROW_SCALE: f32 = 0.5;
+
+/// Event triggered when the player buys a conveyor segment, so it can be paid for
+pub struct OnConveyorPurchased;
+
+/// Event triggered when the player removes a conveyor segment, so it can be refunded
+pub struct OnConveyorRemoved;
+
+/// A resource holding whether the player is placing conveyors, and which way they will run
+pub struct ConveyorBuildMode {
+    pub active: bool,
+
+    /// The sign of the direction placed conveyors carry crates in (1.0 is to the right)
+    pub direction: f32,
+}
+
+impl Default for ConveyorBuildMode {
+    fn default() -> Self {
+        Self {
+            active: false,
+            direction: -1.0,
+        }
+    }
+}
+
+pub struct ConveyorPlugin;
+
+impl Plugin for ConveyorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ConveyorBuildMode::default())
+            .add_event::<OnConveyorPurchased>()
+            .add_event::<OnConveyorRemoved>()
+            .add_enter_system(GameState::Playing, spawn_conveyor_ghost)
+            .add_system(
+                toggle_conveyor_build_mode
+                    .run_in_state(GameState::Playing)
+                    .run_if(is_not_paused),
+            )
+            .add_system(update_conveyor_ghost.run_in_state(GameState::Playing))
+            .add_system(
+                place_conveyors
+                    .run_in_state(GameState::Playing)
+                    .run_if(is_building_conveyors)
+                    .run_if(is_not_paused),
+            )
+            .add_system(move_crates_on_conveyors.run_in_state(GameState::Playing));
+    }
+}
+
+pub fn is_building_conveyors(build_mode: Res<ConveyorBuildMode>) -> bool {
+    build_mode.active
+}
+
+pub fn is_not_building_conveyors(build_mode: Res<ConveyorBuildMode>) -> bool {
+    !build_mode.active
+}
+
+/// Snaps a world position to the grid conveyors are placed on
+fn snap_to_grid(position: Vec2) -> Vec2 {
+    (position / (0.5 * GRID_SIZE)).round() * 0.5 * GRID_SIZE
+}
+
+/// The rotation of the arrow shown on a conveyor running in the given direction
+fn arrow_rotation(direction: f32) -> Quat {
+    Quat::from_rotation_z(-direction * std::f32::consts::FRAC_PI_2)
+}
+
+/// Not a system - spawns the sprite for a conveyor segment, with an arrow showing which way it runs
+fn spawn_conveyor_sprite(
+    commands: &mut Commands,
+    textures: &TextureAssets,
+    location: Vec3,
+    direction: f32,
+    colour: Color,
+) -> Entity {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: colour,
+                custom_size: Some(CONVEYOR_SEGMENT_SIZE),
+                ..default()
+            },
+            transform: Transform::from_translation(location),
+            ..default()
+        })
+        .insert(WorldEntity)
+        .with_children(|conveyor| {
+            conveyor.spawn_bundle(SpriteBundle {
+                texture: textures.up.clone(),
+                sprite: Sprite {
+                    color: colour,
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, 0.01)
+                    .with_rotation(arrow_rotation(direction))
+                    .with_scale(Vec3::splat(CONVEYOR_ARROW_SCALE)),
+                ..default()
+            });
+        })
+        .id()
+}
+
+fn spawn_conveyor_ghost(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    mut build_mode: ResMut<ConveyorBuildMode>,
+) {
+    *build_mode = ConveyorBuildMode::default();
+
+    let ghost = spawn_conveyor_sprite(
+        &mut commands,
+        &textures,
+        Vec3::new(0.0, 0.0, CONVEYOR_Z_POS),
+        build_mode.direction,
+        CONVEYOR_GHOST_COLOUR,
+    );
+
+    commands
+        .entity(ghost)
+        .insert(ConveyorGhost)
+        .insert(Visibility { is_visible: false });
+}
+
+/// Toggles build mode with the build key, and flips the direction of new conveyors
+/// with the rotate key
+fn toggle_conveyor_build_mode(
+    dragging: Res<DraggingBox>,
+    mut build_mode: ResMut<ConveyorBuildMode>,
+    action_state_query: Query<&ActionState<PlayerActions>>,
+) {
+    let action_state = action_state_query.single();
+
+    // don't start building while a crate is being carried, or it could never be dropped
+    if action_state.just_pressed(PlayerActions::Build) && dragging.box_entity.is_none() {
+        build_mode.active = !build_mode.active;
+        info!("Conveyor build mode: {}", build_mode.active);
+    }
+
+    if build_mode.active && action_state.just_pressed(PlayerActions::Rotate) {
+        build_mode.direction = -build_mode.direction;
+    }
+}
+
+/// Shows where a conveyor will be placed while in build mode, in red if the company can't
+/// afford another segment
+#[allow(clippy::type_complexity)]
+fn update_conveyor_ghost(
+    mouse_pos: Res<MousePosition>,
+    build_mode: Res<ConveyorBuildMode>,
+    score: Res<Score>,
+    mut ghosts: Query<
+        (&mut Transform, &mut Visibility, &mut Sprite, &Children),
+        With<ConveyorGhost>,
+    >,
+    mut arrows: Query<(&mut Transform, &mut Sprite), Without<ConveyorGhost>>,
+) {
+    let colour = if score.0 < CONVEYOR_SEGMENT_COST {
+        CONVEYOR_UNAFFORDABLE_GHOST_COLOUR
+    } else {
+        CONVEYOR_GHOST_COLOUR
+    };
+
+    for (mut tx, mut visibility, mut sprite, children) in ghosts.iter_mut() {
+        visibility.is_visible = build_mode.active && mouse_pos.in_screen;
+        if !visibility.is_visible {
+            continue;
+        }
+
+        tx.translation = snap_to_grid(mouse_pos.world).extend(CONVEYOR_Z_POS);
+        if sprite.color != colour {
+            sprite.color = colour;
+        }
+
+        for child in children.iter() {
+            if let Ok((mut arrow_tx, mut arrow_sprite)) = arrows.get_mut(*child) {
+                arrow_tx.rotation = arrow_rotation(build_mode.direction);
+                if arrow_sprite.color != colour {
+                    arrow_sprite.color = colour;
+                }
+            }
+        }
+    }
+}
+
+/// Buys and places a conveyor segment where the player clicks while in build mode, if the
+/// company can afford it, or removes and refunds the conveyor segment that was clicked on
+#[allow(clippy::too_many_arguments)]
+fn place_conveyors(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    mouse_pos: Res<MousePosition>,
+    build_mode: Res<ConveyorBuildMode>,
+    score: Res<Score>,
+    mut purchase_events: EventWriter<OnConveyorPurchased>,
+    mut removed_events: EventWriter<OnConveyorRemoved>,
+    action_state_query: Query<&ActionState<PlayerActions>>,
+    conveyors: Query<(Entity, &Transform), With<ConveyorBelt>>,
+) {
+    let action_state = action_state_query.single();
+    if !action_state.just_pressed(PlayerActions::Click) || !mouse_pos.in_screen {
+        return;
+    }
+
+    let location = snap_to_grid(mouse_pos.world);
+
+    for (entity, tx) in conveyors.iter() {
+        let offset = (tx.translation.truncate() - location).abs();
+        if offset.x < CONVEYOR_SEGMENT_SIZE.x && offset.y < CONVEYOR_SEGMENT_SIZE.y {
+            info!("Removing conveyor {:?}", entity);
+            commands.entity(entity).despawn_recursive();
+            removed_events.send(OnConveyorRemoved);
+            return;
+        }
+    }
+
+    if score.0 < CONVEYOR_SEGMENT_COST {
+        info!("Can't afford a conveyor segment");
+        return;
+    }
+
+    info!("Placing conveyor at {:?}", location);
+    spawn_conveyor(&mut commands, &textures, location, build_mode.direction);
+    purchase_events.send(OnConveyorPurchased);
+}
+
+/// Not a system - spawns a working conveyor segment, without paying for it
+pub fn spawn_conveyor(
+    commands: &mut Commands,
+    textures: &TextureAssets,
+    location: Vec2,
+    direction: f32,
+) {
+    let conveyor = spawn_conveyor_sprite(
+        commands,
+        textures,
+        location.extend(CONVEYOR_Z_POS),
+        direction,
+        CONVEYOR_COLOUR,
+    );
+
+    commands
+        .entity(conveyor)
+        .insert(RigidBody::Static)
+        .insert(CollisionShape::Cuboid {
+            half_extends: (CONVEYOR_SEGMENT_SIZE / 2.0).extend(GRID_SIZE / 2.0),
+            border_radius: None,
+        })
+        .insert(
+            CollisionLayers::none()
+                .with_group(GamePhysicsLayer::Conveyor)
+                .with_mask(GamePhysicsLayer::Crate),
+        )
+        .insert(ConveyorBelt { direction })
+        .insert(HardSurface);
+}
+
+/// Carries crates resting on a conveyor along at a constant speed
+fn move_crates_on_conveyors(
+    mut crates: Query<(&Collisions, &mut Velocity), With<PhysicsCrate>>,
+    conveyors: Query<&ConveyorBelt>,
+) {
+    for (collisions, mut velocity) in crates.iter_mut() {
+        if let Some(conveyor) = collisions
+            .entities()
+            .find_map(|entity| conveyors.get(entity).ok())
+        {
+            velocity.linear.x = conveyor.direction * CONVEYOR_SPEED;
+        }
+    }
+}
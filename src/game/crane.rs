@@ -0,0 +1,340 @@
+use bevy::prelude::*;
+use iyes_loopless::prelude::{AppLooplessStateExt, IntoConditionalSystem};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    game::{
+        clock::GameClock,
+        components::{
+            AnimateWithSpeed, BoxType, CraneHook, CraneHookCrate, CraneTower, PhysicsCrate,
+            ShipHold, Sinking, Wave, WorldEntity,
+        },
+        spawners::{lanes::ShipLane, spawn_physics_crate},
+        ui::Score,
+        warehouse::{Warehouse, WAREHOUSE_HALF_EXTENTS},
+        WAREHOUSE_LOCATION,
+    },
+    loader::TextureAssets,
+    GameState, GRID_SIZE,
+};
+
+/// The x position of the crane tower, on the seaward edge of the warehouse
+pub const CRANE_X: f32 = WAREHOUSE_LOCATION.x + WAREHOUSE_HALF_EXTENTS.x;
+
+/// How far either side of the crane a ship can be for the crane to load it
+const CRANE_REACH: f32 = 3.0 * GRID_SIZE;
+
+const CRANE_BASE_Y: f32 = -2.0 * GRID_SIZE;
+const CRANE_TOWER_HEIGHT: f32 = 3.0 * GRID_SIZE;
+
+/// The height the crane hook carries crates at, just under the arm
+const CRANE_HOOK_Y: f32 = CRANE_BASE_Y + CRANE_TOWER_HEIGHT - 0.5 * GRID_SIZE;
+const CRANE_Z_POS: f32 = 4.0;
+
+/// Where a crate the crane couldn't deliver is put back, just above the warehouse floor
+pub const CRANE_RETURN_LOCATION: Vec3 = Vec3::new(
+    WAREHOUSE_LOCATION.x,
+    WAREHOUSE_LOCATION.y + WAREHOUSE_HALF_EXTENTS.y + GRID_SIZE,
+    5.0,
+);
+
+pub const CRANE_COST: f32 = 100.0;
+pub const CRANE_REPAIR_COST: f32 = 10.0;
+
+/// How long the crane waits between loading crates
+const CRANE_CYCLE_SECONDS: f32 = 4.0;
+
+/// How long it takes the crane to swing a crate from the warehouse to a ship
+const CRANE_SWING_SECONDS: f32 = 1.5;
+
+const CRANE_BREAKDOWN_CHANCE: f64 = 0.15;
+pub const CRANE_REPAIR_SECONDS: f32 = 15.0;
+
+const CRANE_COLOUR: Color = Color::rgb(0.3, 0.25, 0.2);
+const BROKEN_CRANE_COLOUR: Color = Color::rgb(0.6, 0.2, 0.15);
+
+/// Event triggered when the player buys the crane, so it can be paid for
+pub struct OnCranePurchased;
+
+/// Event triggered when the crane is repaired after a breakdown, so the repairs can be paid for
+pub struct OnCraneRepaired;
+
+/// A crate the crane is carrying from the warehouse to a ship
+#[derive(Debug)]
+pub struct CraneLoad {
+    pub box_type: BoxType,
+    pub spawned_at: f64,
+    pub ship: Entity,
+
+    /// Where the hook was when it picked the crate up, which the swing starts from
+    pub from_x: f32,
+    pub timer: Timer,
+}
+
+/// A resource holding the state of the dockside crane
+#[derive(Debug)]
+pub struct Crane {
+    pub owned: bool,
+
+    /// Counts down until the crane looks for another crate to load
+    pub cycle: Timer,
+
+    /// Counts down until the crane is repaired, if it has broken down. The crane stays broken
+    /// after the timer finishes until the repairs can be paid for.
+    pub repair: Option<Timer>,
+
+    pub load: Option<CraneLoad>,
+}
+
+impl Default for Crane {
+    fn default() -> Self {
+        Self {
+            owned: false,
+            cycle: Timer::from_seconds(CRANE_CYCLE_SECONDS, false),
+            repair: None,
+            load: None,
+        }
+    }
+}
+
+pub struct CranePlugin;
+
+impl Plugin for CranePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Crane::default())
+            .add_event::<OnCranePurchased>()
+            .add_event::<OnCraneRepaired>()
+            .add_enter_system(GameState::Playing, reset_crane)
+            .add_system(spawn_crane_on_purchase.run_on_event::<OnCranePurchased>())
+            .add_system(run_crane.run_in_state(GameState::Playing));
+    }
+}
+
+fn reset_crane(mut crane: ResMut<Crane>) {
+    *crane = Crane::default();
+}
+
+fn spawn_crane_on_purchase(mut commands: Commands) {
+    spawn_crane(&mut commands, false);
+}
+
+/// Not a system - spawns the crane tower and hook, without paying for them
+pub fn spawn_crane(commands: &mut Commands, is_broken: bool) {
+    // the tower, with an arm reaching out over the shipping lanes
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: if is_broken {
+                    BROKEN_CRANE_COLOUR
+                } else {
+                    CRANE_COLOUR
+                },
+                custom_size: Some(Vec2::new(0.4 * GRID_SIZE, CRANE_TOWER_HEIGHT)),
+                ..default()
+            },
+            transform: Transform::from_xyz(
+                CRANE_X,
+                CRANE_BASE_Y + 0.5 * CRANE_TOWER_HEIGHT,
+                CRANE_Z_POS,
+            ),
+            ..default()
+        })
+        .insert(CraneTower)
+        .insert(WorldEntity)
+        .with_children(|tower| {
+            let arm_length = WAREHOUSE_HALF_EXTENTS.x + CRANE_REACH;
+
+            tower.spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: CRANE_COLOUR,
+                    custom_size: Some(Vec2::new(arm_length, 0.25 * GRID_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(
+                    0.5 * (CRANE_REACH - WAREHOUSE_HALF_EXTENTS.x),
+                    0.5 * CRANE_TOWER_HEIGHT,
+                    0.0,
+                ),
+                ..default()
+            });
+        });
+
+    // the hook, which carries crates along the arm
+    commands
+        .spawn_bundle(SpatialBundle {
+            transform: Transform::from_xyz(CRANE_X, CRANE_HOOK_Y, CRANE_Z_POS + 0.1),
+            ..default()
+        })
+        .insert(CraneHook)
+        .insert(WorldEntity)
+        .with_children(|hook| {
+            hook.spawn_bundle(SpriteBundle {
+                visibility: Visibility { is_visible: false },
+                transform: Transform::from_xyz(0.0, -0.5 * GRID_SIZE, 0.0),
+                ..default()
+            })
+            .insert(CraneHookCrate);
+        });
+}
+
+/// Loads ships passing under the crane with matching crates from the warehouse
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn run_crane(
+    mut commands: Commands,
+    time: Res<GameClock>,
+    textures: Res<TextureAssets>,
+    warehouse: Res<Warehouse>,
+    score: Res<Score>,
+    mut crane: ResMut<Crane>,
+    mut repaired_events: EventWriter<OnCraneRepaired>,
+    ships: Query<
+        (Entity, &Transform, &AnimateWithSpeed, &ShipLane, &Children),
+        (With<Wave>, Without<Sinking>),
+    >,
+    holds: Query<&ShipHold>,
+    physics_crates: Query<&PhysicsCrate>,
+    mut hooks: Query<&mut Transform, (With<CraneHook>, Without<Wave>)>,
+    mut hook_crates: Query<
+        (&mut Handle<Image>, &mut Sprite, &mut Visibility),
+        With<CraneHookCrate>,
+    >,
+    mut towers: Query<&mut Sprite, (With<CraneTower>, Without<CraneHookCrate>)>,
+) {
+    if !crane.owned {
+        return;
+    }
+
+    // repair the crane if it has broken down, once the company can pay for the repairs
+    if let Some(repair) = crane.repair.as_mut() {
+        repair.tick(time.delta());
+        if !repair.finished() || score.0 < CRANE_REPAIR_COST {
+            return;
+        }
+
+        info!("Crane repaired");
+        crane.repair = None;
+        repaired_events.send(OnCraneRepaired);
+        for mut sprite in towers.iter_mut() {
+            sprite.color = CRANE_COLOUR;
+        }
+    }
+
+    // swing the current load over to the ship it is destined for, then drop it
+    if let Some(load) = crane.load.as_mut() {
+        load.timer.tick(time.delta());
+
+        let target = ships.get(load.ship).ok();
+        let target_x = target.map_or(load.from_x, |(_, tx, _, _, _)| tx.translation.x);
+        let hook_x = load.from_x + (target_x - load.from_x) * load.timer.percent();
+
+        for mut hook_tx in hooks.iter_mut() {
+            hook_tx.translation.x = hook_x;
+        }
+
+        if !load.timer.finished() {
+            return;
+        }
+
+        // the crate keeps moving with the ship, so it lands on the deck. If the ship has gone
+        // the crate is put back in the warehouse instead.
+        let (location, velocity) = match target {
+            Some((_, _, animation, lane, _)) => (
+                Vec3::new(hook_x, CRANE_HOOK_Y - 0.5 * GRID_SIZE, 5.0),
+                Vec2::new(animation.speed * lane.direction.sign(), 0.0),
+            ),
+            None => (CRANE_RETURN_LOCATION, Vec2::ZERO),
+        };
+
+        let sprite = commands
+            .spawn_bundle(SpriteBundle {
+                texture: load.box_type.get_image(&textures),
+                transform: Transform::from_translation(location),
+                ..default()
+            })
+            .insert(WorldEntity)
+            .id();
+        spawn_physics_crate(
+            &mut commands,
+            sprite,
+            load.box_type,
+            load.spawned_at,
+            velocity,
+        );
+
+        info!("Crane dropped {:?}", load.box_type);
+
+        crane.load = None;
+        crane.cycle.reset();
+        for (_, _, mut visibility) in hook_crates.iter_mut() {
+            visibility.is_visible = false;
+        }
+
+        if thread_rng().gen_bool(CRANE_BREAKDOWN_CHANCE) {
+            warn!("Crane broke down");
+            crane.repair = Some(Timer::from_seconds(CRANE_REPAIR_SECONDS, false));
+
+            for mut sprite in towers.iter_mut() {
+                sprite.color = BROKEN_CRANE_COLOUR;
+            }
+        }
+
+        return;
+    }
+
+    crane.cycle.tick(time.delta());
+    if !crane.cycle.finished() {
+        return;
+    }
+
+    // look for a ship under the crane with a demand the warehouse can meet
+    for (ship_entity, ship_tx, _, _, children) in ships.iter() {
+        if (ship_tx.translation.x - CRANE_X).abs() > CRANE_REACH {
+            continue;
+        }
+
+        let unmet_demands = match children.iter().find_map(|child| holds.get(*child).ok()) {
+            Some(hold) => hold.get_unmet_demands(),
+            None => continue,
+        };
+
+        // rotten crates are left for the player to deal with, rather than loaded for a penalty
+        let now = time.seconds_since_startup();
+        let stock = warehouse
+            .crates
+            .iter()
+            .find_map(|(crate_entity, box_type)| {
+                let physics_crate = physics_crates.get(*crate_entity).ok()?;
+                (unmet_demands.contains(box_type) && !physics_crate.is_rotten(now)).then_some((
+                    *crate_entity,
+                    *box_type,
+                    physics_crate.spawned_at,
+                ))
+            });
+        let (crate_entity, box_type, spawned_at) = match stock {
+            Some(stock) => stock,
+            None => continue,
+        };
+
+        info!("Crane picking up {:?} for ship {:?}", box_type, ship_entity);
+
+        commands.entity(crate_entity).despawn_recursive();
+        crane.load = Some(CraneLoad {
+            box_type,
+            spawned_at,
+            ship: ship_entity,
+            from_x: hooks
+                .iter()
+                .next()
+                .map_or(CRANE_X, |hook_tx| hook_tx.translation.x),
+            timer: Timer::from_seconds(CRANE_SWING_SECONDS, false),
+        });
+
+        for (mut image, mut sprite, mut visibility) in hook_crates.iter_mut() {
+            *image = box_type.get_image(&textures);
+            sprite.color = box_type.spoilage_tint(now - spawned_at);
+            visibility.is_visible = true;
+        }
+
+        return;
+    }
+}
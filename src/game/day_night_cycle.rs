@@ -1,39 +1,46 @@
 use bevy::prelude::*;
 use chrono::{Duration, NaiveDate};
 use iyes_loopless::prelude::{AppLooplessStateExt, IntoConditionalSystem};
-use rand::{thread_rng, Rng, SeedableRng};
+use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 
 use crate::{
+    game::clock::GameClock,
     loader::{AnimationAssets, TextureAssets},
     GameState, HEIGHT, WIDTH,
 };
 
 use super::{
-    components::{Star, Sun, Torch, WorldEntity},
+    calendar::Calendar,
+    components::{Background, Star, Sun, Torch, WorldEntity},
+    seasons::{self, Season},
+    weather::{Weather, WeatherForecast},
     Animation,
 };
 
-pub struct OnRainStart;
-
-pub struct OnRainEnd;
-
 const RANDOM_SEED: u64 = 349678046248609346;
 
-const TORCH_THRESHOLD: f32 = 1.0;
+/// How bright the sky can be before the torches are lit
+pub const TORCH_THRESHOLD: f32 = 1.0;
+
+/// How long before sunset torches are lit, and how long after sunrise they are put out
+const TORCH_DUSK_HOURS: f32 = 0.5;
 
 // The amount of world time that elapses per game second
-const TIME_OF_DAY_HOURS_PER_GAME_SECONDS: f32 = 0.5;
+pub const TIME_OF_DAY_HOURS_PER_GAME_SECONDS: f32 = 0.5;
 
 const NUM_COLOURS: usize = 8;
 const HOURS_PER_COLOUR: f32 = 24.0 / (NUM_COLOURS as f32);
 
-const SUN_UP: f32 = 6.0;
-const SUN_DOWN: f32 = 19.0;
+/// The sunrise and sunset the sky palettes are drawn for. The palettes are stretched to fit
+/// the real sunrise and sunset on each day.
+const PALETTE_SUNRISE: f32 = 6.0;
+const PALETTE_SUNSET: f32 = 19.0;
 
-const STAR_SPEED: f32 = -2.3;
+/// How long it takes the stars to come out after sunset
+const STAR_FADE_IN_HOURS: f32 = 4.0;
 
-const CHANCE_OF_SUN: f64 = 0.8;
+const STAR_SPEED: f32 = -2.3;
 
 // Note for smooth lerping, these palettes should start and end on the same colour as each other
 const SUNNY_COLOR_CYCLE: [Vec3; NUM_COLOURS] = [
@@ -58,6 +65,39 @@ const STORMY_COLOR_CYCLE: [Vec3; NUM_COLOURS] = [
     /*  9pm */ Vec3::new(0.18, 0.18, 0.25),
 ];
 
+const FOGGY_COLOR_CYCLE: [Vec3; NUM_COLOURS] = [
+    /*  0am */ Vec3::new(0.1, 0.1, 0.2),
+    /*  3am */ Vec3::new(0.15, 0.15, 0.25),
+    /*  6am */ Vec3::new(0.4, 0.4, 0.45),
+    /*  9am */ Vec3::new(0.6, 0.6, 0.62),
+    /* 12pm */ Vec3::new(0.68, 0.68, 0.7),
+    /*  3pm */ Vec3::new(0.62, 0.62, 0.65),
+    /*  6pm */ Vec3::new(0.38, 0.38, 0.42),
+    /*  9pm */ Vec3::new(0.18, 0.18, 0.25),
+];
+
+const HEATWAVE_COLOR_CYCLE: [Vec3; NUM_COLOURS] = [
+    /*  0am */ Vec3::new(0.1, 0.1, 0.2),
+    /*  3am */ Vec3::new(0.15, 0.15, 0.25),
+    /*  6am */ Vec3::new(0.55, 0.35, 0.4),
+    /*  9am */ Vec3::new(0.7, 0.6, 0.5),
+    /* 12pm */ Vec3::new(0.85, 0.75, 0.5),
+    /*  3pm */ Vec3::new(0.9, 0.65, 0.4),
+    /*  6pm */ Vec3::new(0.6, 0.3, 0.3),
+    /*  9pm */ Vec3::new(0.18, 0.18, 0.25),
+];
+
+const SNOWY_COLOR_CYCLE: [Vec3; NUM_COLOURS] = [
+    /*  0am */ Vec3::new(0.1, 0.1, 0.2),
+    /*  3am */ Vec3::new(0.15, 0.15, 0.25),
+    /*  6am */ Vec3::new(0.35, 0.35, 0.45),
+    /*  9am */ Vec3::new(0.65, 0.7, 0.8),
+    /* 12pm */ Vec3::new(0.75, 0.8, 0.88),
+    /*  3pm */ Vec3::new(0.65, 0.7, 0.8),
+    /*  6pm */ Vec3::new(0.35, 0.35, 0.45),
+    /*  9pm */ Vec3::new(0.18, 0.18, 0.25),
+];
+
 pub const START_DATE_YMD: [u32; 3] = [1883, 6, 11];
 
 pub fn get_start_date() -> NaiveDate {
@@ -72,109 +112,139 @@ pub struct DayNightCyclePlugin;
 
 impl Plugin for DayNightCyclePlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(SkyColourCycles::default())
-            .insert_resource(TimeOfDay {
-                time_of_day: 5.8,
-                today: get_start_date(),
-            })
-            .add_event::<OnSunEvent>()
-            .add_system(day_night_cycle.run_in_state(GameState::Playing))
-            .add_system(torch_visibility.run_in_state(GameState::Playing))
-            .add_system(star_and_sun_spawner.run_in_state(GameState::Playing))
-            .add_system(sun_movement.run_in_state(GameState::Playing))
-            .add_system(star_movement.run_in_state(GameState::Playing))
-            .add_enter_system(GameState::Playing, reset_day_night_cycle);
+        app.insert_resource(TimeOfDay {
+            time_of_day: 5.8,
+            today: get_start_date(),
+        })
+        .add_event::<OnSunEvent>()
+        .add_event::<OnNewDay>()
+        .add_system(day_night_cycle.run_in_state(GameState::Playing))
+        .add_system(torch_visibility.run_in_state(GameState::Playing))
+        .add_system(star_and_sun_spawner.run_in_state(GameState::Playing))
+        .add_system(sun_movement.run_in_state(GameState::Playing))
+        .add_system(star_movement.run_in_state(GameState::Playing))
+        .add_system(tint_background.run_in_state(GameState::Playing))
+        .add_enter_system(GameState::Playing, reset_day_night_cycle);
     }
 }
 
 struct OnSunEvent(pub bool);
 
-struct SkyColourCycles {
-    pub sunny: [Vec3; NUM_COLOURS],
-    pub stormy: [Vec3; NUM_COLOURS],
-    pub is_sunny: bool,
-}
-
-impl Default for SkyColourCycles {
-    fn default() -> Self {
-        Self {
-            sunny: SUNNY_COLOR_CYCLE,
-            stormy: STORMY_COLOR_CYCLE,
-            is_sunny: true,
-        }
-    }
-}
+/// Event triggered at midnight with the new date
+pub struct OnNewDay(pub NaiveDate);
 
 pub struct TimeOfDay {
     pub time_of_day: f32,
     pub today: NaiveDate,
 }
 
+impl TimeOfDay {
+    /// Whether the sun is down
+    pub fn is_night(&self) -> bool {
+        self.is_night_at(self.time_of_day)
+    }
+
+    /// Whether the sun will be down the given number of game seconds from now. Any change in
+    /// the length of the day over that time is ignored.
+    pub fn is_night_in(&self, seconds: f64) -> bool {
+        let hours = seconds as f32 * TIME_OF_DAY_HOURS_PER_GAME_SECONDS;
+        self.is_night_at((self.time_of_day + hours).rem_euclid(24.0))
+    }
+
+    fn is_night_at(&self, hour: f32) -> bool {
+        hour < seasons::sunrise(self.today) || hour >= seasons::sunset(self.today)
+    }
+}
+
+/// Starts each game just before dawn
 fn reset_day_night_cycle(mut cycle: ResMut<TimeOfDay>) {
-    cycle.time_of_day = 5.8;
+    cycle.time_of_day = seasons::sunrise(cycle.today) - 0.2;
+}
+
+/// Maps the time of day onto the hours the sky palettes are drawn for, so that dawn and dusk
+/// in the palette line up with the real sunrise and sunset
+fn palette_hour(time_of_day: f32, sunrise: f32, sunset: f32) -> f32 {
+    if time_of_day < sunrise {
+        time_of_day * PALETTE_SUNRISE / sunrise
+    } else if time_of_day < sunset {
+        PALETTE_SUNRISE
+            + (time_of_day - sunrise) * (PALETTE_SUNSET - PALETTE_SUNRISE) / (sunset - sunrise)
+    } else {
+        PALETTE_SUNSET + (time_of_day - sunset) * (24.0 - PALETTE_SUNSET) / (24.0 - sunset)
+    }
+}
+
+/// The sky colours to cycle through over a day with the given weather
+fn sky_palette(weather: Weather) -> [Vec3; NUM_COLOURS] {
+    match weather {
+        Weather::Sunny => SUNNY_COLOR_CYCLE,
+        Weather::Stormy => STORMY_COLOR_CYCLE,
+        Weather::Foggy => FOGGY_COLOR_CYCLE,
+        Weather::Heatwave => HEATWAVE_COLOR_CYCLE,
+        Weather::Snowy => SNOWY_COLOR_CYCLE,
+    }
 }
 
 fn day_night_cycle(
-    time: Res<Time>,
-    mut cycle: ResMut<SkyColourCycles>,
+    time: Res<GameClock>,
+    forecast: Res<WeatherForecast>,
+    calendar: Res<Calendar>,
     mut clear_colour: ResMut<ClearColor>,
     mut time_of_day: ResMut<TimeOfDay>,
     mut sun_events: EventWriter<OnSunEvent>,
-    mut rain_start_events: EventWriter<OnRainStart>,
-    mut rain_stop_events: EventWriter<OnRainEnd>,
+    mut new_day_events: EventWriter<OnNewDay>,
 ) {
     let dt = time.delta_seconds();
     let elapsed = dt * TIME_OF_DAY_HOURS_PER_GAME_SECONDS;
-    let mut rng = thread_rng();
 
     let prev_time_of_day = time_of_day.time_of_day;
     time_of_day.time_of_day = (time_of_day.time_of_day + elapsed) % 24.0;
 
     // check if we've wrapped over midnight
     if prev_time_of_day > 23.0 && time_of_day.time_of_day < 1.0 {
-        // true if we've just wrapped day, we need to toggle the colour pattern
-        cycle.is_sunny = rng.gen_bool(CHANCE_OF_SUN);
-
         // increment the date
         time_of_day.today += Duration::days(1);
+        new_day_events.send(OnNewDay(time_of_day.today));
     }
 
-    if prev_time_of_day < 18.0 && time_of_day.time_of_day >= 18.0 {
+    let sunrise = seasons::sunrise(time_of_day.today);
+    let sunset = seasons::sunset(time_of_day.today);
+
+    if prev_time_of_day < sunset && time_of_day.time_of_day >= sunset {
         sun_events.send(OnSunEvent(false));
-        rain_stop_events.send(OnRainEnd);
     }
 
-    if prev_time_of_day < 6.0 && time_of_day.time_of_day >= 6.0 {
+    if prev_time_of_day < sunrise && time_of_day.time_of_day >= sunrise {
         sun_events.send(OnSunEvent(true));
-        if !cycle.is_sunny {
-            rain_start_events.send(OnRainStart);
-        }
     }
 
-    let from_idx = (time_of_day.time_of_day / HOURS_PER_COLOUR).floor() as usize;
+    let hour = palette_hour(time_of_day.time_of_day, sunrise, sunset);
+    let from_idx = (hour / HOURS_PER_COLOUR).floor() as usize % NUM_COLOURS;
     let to_idx = (from_idx + 1) % NUM_COLOURS;
 
-    let cycle_data = if cycle.is_sunny {
-        cycle.sunny
-    } else {
-        cycle.stormy
-    };
+    let cycle_data = sky_palette(forecast.today());
 
     let colour = cycle_data[from_idx].lerp(
         cycle_data[to_idx],
-        (time_of_day.time_of_day % HOURS_PER_COLOUR) / HOURS_PER_COLOUR,
-    );
+        (hour % HOURS_PER_COLOUR) / HOURS_PER_COLOUR,
+    ) * Season::from_date(time_of_day.today).sky_tint()
+        * calendar.sky_tint();
 
     clear_colour.0 = Color::from(colour.extend(1.0));
 }
 
+/// Lights the torches around dusk, which comes earlier in winter, or whenever the sky is dark
 fn torch_visibility(
+    tod: Res<TimeOfDay>,
     clear_colour: Res<ClearColor>,
     animations: Res<AnimationAssets>,
     mut torches: Query<&mut Handle<Animation>, With<Torch>>,
 ) {
+    let daylight = (seasons::sunrise(tod.today) + TORCH_DUSK_HOURS)
+        ..(seasons::sunset(tod.today) - TORCH_DUSK_HOURS);
     let colour_darkness = clear_colour.0.r() + clear_colour.0.g() + clear_colour.0.b();
-    let anim = if colour_darkness < TORCH_THRESHOLD {
+
+    let anim = if !daylight.contains(&tod.time_of_day) || colour_darkness < TORCH_THRESHOLD {
         animations.torch.clone()
     } else {
         animations.torch_off.clone()
@@ -189,7 +259,7 @@ fn star_and_sun_spawner(
     mut commands: Commands,
     mut sun_events: EventReader<OnSunEvent>,
     textures: Res<TextureAssets>,
-    cycle: Res<SkyColourCycles>,
+    forecast: Res<WeatherForecast>,
     suns: Query<Entity, With<Sun>>,
     stars: Query<Entity, With<Star>>,
 ) {
@@ -201,7 +271,7 @@ fn star_and_sun_spawner(
                     commands.entity(star).despawn();
                 }
 
-                if cycle.is_sunny {
+                if forecast.today().shows_sun() {
                     commands
                         .spawn_bundle(SpriteSheetBundle {
                             texture_atlas: textures.sun.clone(),
@@ -253,7 +323,9 @@ fn get_sun_y(t: f32) -> f32 {
 }
 
 fn sun_movement(tod: Res<TimeOfDay>, mut suns: Query<&mut Transform, With<Sun>>) {
-    let proportion_through_sun_up_time = (tod.time_of_day - SUN_UP) / (SUN_DOWN - SUN_UP);
+    let sunrise = seasons::sunrise(tod.today);
+    let proportion_through_sun_up_time =
+        (tod.time_of_day - sunrise) / seasons::day_length(tod.today);
     let x = get_sun_x(proportion_through_sun_up_time);
     let y = get_sun_y(proportion_through_sun_up_time);
 
@@ -265,14 +337,17 @@ fn sun_movement(tod: Res<TimeOfDay>, mut suns: Query<&mut Transform, With<Sun>>)
 
 fn star_movement(
     tod: Res<TimeOfDay>,
-    time: Res<Time>,
+    time: Res<GameClock>,
     mut stars: Query<(&mut Sprite, &mut Transform), With<Star>>,
 ) {
-    let alpha = if tod.time_of_day > SUN_DOWN {
+    let sunrise = seasons::sunrise(tod.today);
+    let sunset = seasons::sunset(tod.today);
+
+    let alpha = if tod.time_of_day > sunset {
         // fading in
-        ((tod.time_of_day - SUN_DOWN) / (23.0 - SUN_DOWN)).clamp(0.0, 1.0)
+        ((tod.time_of_day - sunset) / STAR_FADE_IN_HOURS).clamp(0.0, 1.0)
     } else {
-        1.0 - (tod.time_of_day / (SUN_UP - 2.0)).clamp(0.0, 1.0)
+        1.0 - (tod.time_of_day / (sunrise - 2.0)).clamp(0.0, 1.0)
     };
 
     let dt = time.delta_seconds();
@@ -283,3 +358,14 @@ fn star_movement(
         tx.translation.x += dt * STAR_SPEED;
     }
 }
+
+/// Tints the dock to match the season
+fn tint_background(tod: Res<TimeOfDay>, mut backgrounds: Query<&mut Sprite, With<Background>>) {
+    let tint = Season::from_date(tod.today).background_tint();
+
+    for mut sprite in backgrounds.iter_mut() {
+        if sprite.color != tint {
+            sprite.color = tint;
+        }
+    }
+}
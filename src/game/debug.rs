@@ -1,28 +1,37 @@
 use bevy::prelude::*;
 use bevy_prototype_debug_lines::{DebugLines, DebugLinesPlugin};
 
-use crate::WIDTH;
+use crate::GRID_SIZE;
 
-use super::actions::{CART_MAX_Y, CART_MIN_Y};
+use super::{components::Cart, spawners::CartTypes};
 
 pub struct DebugPlugin;
 
 impl Plugin for DebugPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(DebugLinesPlugin::default())
-            .add_system(draw_drop_regions);
+            .add_system(draw_cart_slots);
     }
 }
 
-fn draw_drop_regions(mut lines: ResMut<DebugLines>) {
-    lines.line(
-        Vec3::new(-WIDTH / 2.0, CART_MAX_Y, 0.1),
-        Vec3::new(WIDTH / 2.0, CART_MAX_Y, 0.1),
-        0.0,
-    );
-    lines.line(
-        Vec3::new(-WIDTH / 2.0, CART_MIN_Y, 0.1),
-        Vec3::new(WIDTH / 2.0, CART_MIN_Y, 0.1),
-        0.0,
-    );
+/// Draws the pickup region of every cargo slot on every cart
+fn draw_cart_slots(
+    mut lines: ResMut<DebugLines>,
+    cart_types: Res<CartTypes>,
+    carts: Query<(&Transform, &Cart)>,
+) {
+    for (cart_tx, cart) in carts.iter() {
+        let half_size = 0.5 * GRID_SIZE * cart_tx.scale.truncate();
+
+        for offset in cart_types.get(cart.cart_type).slots.iter() {
+            let centre = cart_tx.translation.truncate() + *offset * cart_tx.scale.truncate();
+            let min = (centre - half_size).extend(10.0);
+            let max = (centre + half_size).extend(10.0);
+
+            lines.line(min, Vec3::new(max.x, min.y, 10.0), 0.0);
+            lines.line(Vec3::new(max.x, min.y, 10.0), max, 0.0);
+            lines.line(max, Vec3::new(min.x, max.y, 10.0), 0.0);
+            lines.line(Vec3::new(min.x, max.y, 10.0), min, 0.0);
+        }
+    }
 }
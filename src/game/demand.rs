@@ -0,0 +1,265 @@
+use std::ops::RangeInclusive;
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, seq::SliceRandom, Rng, RngCore};
+
+use crate::game::{components::BoxType, factory::recipes::Recipes};
+
+/// How often the difficulty scaled generator allows ships to demand one more crate
+const DIFFICULTY_STEP_SECONDS: f64 = 180.0;
+
+/// The information a demand generator can use when deciding what a ship wants
+pub struct DemandContext<'a> {
+    /// How long ships have been arriving for, in seconds
+    pub elapsed: f64,
+
+    /// The recipes the factory can currently produce
+    pub recipes: &'a Recipes,
+}
+
+/// Decides which goods a ship demands when it is scheduled
+pub trait DemandGenerator: Send + Sync {
+    fn generate(&self, rng: &mut dyn RngCore, context: &DemandContext) -> Vec<BoxType>;
+}
+
+/// Picks each demand from a weighted table of goods
+#[derive(Clone, Debug)]
+pub struct WeightedTableDemands {
+    pub weights: Vec<(BoxType, u32)>,
+    pub count: RangeInclusive<usize>,
+}
+
+impl Default for WeightedTableDemands {
+    fn default() -> Self {
+        Self {
+            weights: vec![
+                (BoxType::Apples, 1),
+                (BoxType::Grapes, 1),
+                (BoxType::Honey, 1),
+                (BoxType::Wheat, 1),
+                (BoxType::Cider, 3),
+                (BoxType::Wine, 3),
+                (BoxType::Mead, 3),
+                (BoxType::Beer, 3),
+            ],
+            count: 1..=3,
+        }
+    }
+}
+
+impl WeightedTableDemands {
+    /// Picks `count` goods from the weighted table
+    fn pick(&self, rng: &mut dyn RngCore, count: usize) -> Vec<BoxType> {
+        let dist = match WeightedIndex::new(self.weights.iter().map(|(_, weight)| *weight)) {
+            Ok(dist) => dist,
+            Err(_) => return vec![],
+        };
+
+        (0..count)
+            .map(|_| self.weights[dist.sample(rng)].0)
+            .collect()
+    }
+}
+
+impl DemandGenerator for WeightedTableDemands {
+    fn generate(&self, rng: &mut dyn RngCore, _context: &DemandContext) -> Vec<BoxType> {
+        let count = rng.gen_range(self.count.clone());
+        self.pick(rng, count)
+    }
+}
+
+/// Picks demands from a weighted table, with ships demanding more crates as the game goes on
+#[derive(Clone, Debug)]
+pub struct DifficultyScaledDemands {
+    pub table: WeightedTableDemands,
+    pub max_count: usize,
+}
+
+impl Default for DifficultyScaledDemands {
+    fn default() -> Self {
+        Self {
+            table: WeightedTableDemands {
+                count: 1..=2,
+                ..Default::default()
+            },
+            max_count: 4,
+        }
+    }
+}
+
+impl DemandGenerator for DifficultyScaledDemands {
+    fn generate(&self, rng: &mut dyn RngCore, context: &DemandContext) -> Vec<BoxType> {
+        let extra = (context.elapsed / DIFFICULTY_STEP_SECONDS).floor().max(0.0) as usize;
+        let min = (*self.table.count.start() + extra / 2).min(self.max_count);
+        let max = (*self.table.count.end() + extra)
+            .min(self.max_count)
+            .max(min);
+
+        let count = rng.gen_range(min..=max);
+        self.table.pick(rng, count)
+    }
+}
+
+/// Wraps another generator, making sure ships never demand a good that can't be
+/// delivered by a cart or made using the current recipes
+pub struct RecipeAwareDemands(pub Box<dyn DemandGenerator>);
+
+impl RecipeAwareDemands {
+    /// The goods which can be made, i.e. recipe inputs (delivered by cart) and outputs
+    fn available_goods(recipes: &Recipes) -> Vec<BoxType> {
+        let mut goods = vec![];
+
+        for (inputs, output) in recipes.0.iter() {
+            for good in [inputs.0, inputs.1, *output] {
+                if !goods.contains(&good) {
+                    goods.push(good);
+                }
+            }
+        }
+
+        goods
+    }
+}
+
+impl DemandGenerator for RecipeAwareDemands {
+    fn generate(&self, rng: &mut dyn RngCore, context: &DemandContext) -> Vec<BoxType> {
+        let available = Self::available_goods(context.recipes);
+
+        let mut demands = self.0.generate(rng, context);
+        demands.retain(|demand| available.contains(demand));
+
+        // if everything was filtered out, demand something we know can be made
+        if demands.is_empty() {
+            if let Some(good) = available.choose(rng) {
+                demands.push(*good);
+            }
+        }
+
+        demands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::utils::HashMap;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::game::factory::recipes::RecipeInputs;
+
+    const SAMPLES: usize = 500;
+
+    fn rng() -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(42)
+    }
+
+    fn context(elapsed: f64, recipes: &Recipes) -> DemandContext<'_> {
+        DemandContext { elapsed, recipes }
+    }
+
+    fn cider_only() -> Recipes {
+        let mut hm = HashMap::new();
+        hm.insert(
+            RecipeInputs(BoxType::Glassware, BoxType::Apples),
+            BoxType::Cider,
+        );
+        Recipes(hm)
+    }
+
+    #[test]
+    fn weighted_table_picks_within_count_from_table() {
+        let generator = WeightedTableDemands {
+            weights: vec![(BoxType::Beer, 1), (BoxType::Wine, 3)],
+            count: 2..=3,
+        };
+        let recipes = Recipes::default();
+        let context = context(0.0, &recipes);
+        let mut rng = rng();
+
+        for _ in 0..SAMPLES {
+            let demands = generator.generate(&mut rng, &context);
+            assert!((2..=3).contains(&demands.len()));
+            assert!(demands
+                .iter()
+                .all(|d| *d == BoxType::Beer || *d == BoxType::Wine));
+        }
+    }
+
+    #[test]
+    fn weighted_table_respects_weights() {
+        let generator = WeightedTableDemands {
+            weights: vec![(BoxType::Beer, 1), (BoxType::Wine, 0)],
+            count: 1..=1,
+        };
+        let recipes = Recipes::default();
+        let context = context(0.0, &recipes);
+        let mut rng = rng();
+
+        for _ in 0..SAMPLES {
+            assert_eq!(generator.generate(&mut rng, &context), vec![BoxType::Beer]);
+        }
+    }
+
+    #[test]
+    fn weighted_table_with_no_weights_demands_nothing() {
+        let generator = WeightedTableDemands {
+            weights: vec![],
+            count: 1..=3,
+        };
+        let recipes = Recipes::default();
+        let context = context(0.0, &recipes);
+
+        assert!(generator.generate(&mut rng(), &context).is_empty());
+    }
+
+    #[test]
+    fn difficulty_scaled_demands_grow_with_elapsed_time() {
+        let generator = DifficultyScaledDemands::default();
+        let recipes = Recipes::default();
+        let mut rng = rng();
+
+        let early = context(0.0, &recipes);
+        for _ in 0..SAMPLES {
+            assert!((1..=2).contains(&generator.generate(&mut rng, &early).len()));
+        }
+
+        let late = context(10.0 * DIFFICULTY_STEP_SECONDS, &recipes);
+        for _ in 0..SAMPLES {
+            let count = generator.generate(&mut rng, &late).len();
+            assert!((3..=generator.max_count).contains(&count));
+        }
+    }
+
+    #[test]
+    fn recipe_aware_demands_only_ask_for_goods_that_can_be_made() {
+        let generator = RecipeAwareDemands(Box::new(WeightedTableDemands::default()));
+        let recipes = cider_only();
+        let context = context(0.0, &recipes);
+        let allowed = [BoxType::Glassware, BoxType::Apples, BoxType::Cider];
+        let mut rng = rng();
+
+        for _ in 0..SAMPLES {
+            let demands = generator.generate(&mut rng, &context);
+            assert!(!demands.is_empty());
+            assert!(demands.iter().all(|d| allowed.contains(d)));
+        }
+    }
+
+    #[test]
+    fn recipe_aware_demands_fall_back_when_everything_is_filtered() {
+        let generator = RecipeAwareDemands(Box::new(WeightedTableDemands {
+            weights: vec![(BoxType::Mead, 1)],
+            count: 1..=3,
+        }));
+        let recipes = cider_only();
+        let context = context(0.0, &recipes);
+        let allowed = [BoxType::Glassware, BoxType::Apples, BoxType::Cider];
+        let mut rng = rng();
+
+        for _ in 0..SAMPLES {
+            let demands = generator.generate(&mut rng, &context);
+            assert_eq!(demands.len(), 1);
+            assert!(allowed.contains(&demands[0]));
+        }
+    }
+}
@@ -8,13 +8,20 @@ use crate::{
     GRID_SIZE,
 };
 use bevy::prelude::*;
+use std::time::Duration;
 
 pub struct OnDropInFactoryInput {
     pub box_type: BoxType,
+
+    /// When the crate dropped in the factory was spawned, so it keeps its age if rejected
+    pub spawned_at: f64,
 }
 
 pub struct OnFactoryStartProducing {
     pub box_type: BoxType,
+
+    /// How far through production the factory already is, when resuming a restored game
+    pub elapsed: Duration,
 }
 
 pub struct OnFactoryFinishProducing {
@@ -25,7 +32,8 @@ pub struct OnFactoryQueueItem {
     pub box_type: BoxType,
 }
 
-pub struct OnIncorrectFactoryRecipe(pub BoxType, pub BoxType, pub Vec3);
+/// The two inputs that don't make a recipe, where to throw them out, and when each was spawned
+pub struct OnIncorrectFactoryRecipe(pub BoxType, pub BoxType, pub Vec3, pub [f64; 2]);
 
 pub struct OnIncorrectFactoryRecipeEffects;
 
@@ -70,8 +78,9 @@ pub fn reject_crates_on_incorrect_input(
             })
             .id();
 
-        spawn_physics_crate(&mut commands, e1, evt.0, Vec2::new(-200.0, 50.0));
-        spawn_physics_crate(&mut commands, e2, evt.1, Vec2::new(200.0, 50.0));
+        // rejected crates keep their age, so the factory can't be used to freshen them up
+        spawn_physics_crate(&mut commands, e1, evt.0, evt.3[0], Vec2::new(-200.0, 50.0));
+        spawn_physics_crate(&mut commands, e2, evt.1, evt.3[1], Vec2::new(200.0, 50.0));
         play_incorrect_recipe_effects.send(OnIncorrectFactoryRecipeEffects);
     }
 }
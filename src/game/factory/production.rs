@@ -1,8 +1,13 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
 use crate::{
     game::{
-        components::{BoxType, CountDownTimer, FactoryProductionIndicator, WorldEntity},
+        clock::GameClock,
+        components::{
+            BoxType, CountDownTimer, FactoryProductionIndicator, LandingFromFactory, WorldEntity,
+        },
         factory::utils::new_timer,
         spawners::spawn_physics_crate,
     },
@@ -27,17 +32,18 @@ pub const FACTORY_OUTPUT_INDICATOR_LOCATION: Vec3 =
 #[derive(Default, Debug)]
 pub struct Factory {
     pub inputs: [Option<BoxType>; 2],
+
+    /// When each input crate was spawned, so rejected inputs can be given back at the same age
+    pub inputs_spawned_at: [f64; 2],
     pub output_queue: Vec<BoxType>,
     pub is_producing: bool,
 }
 
 impl Factory {
-    pub fn drop(&mut self, box_type: BoxType) {
-        if self.inputs[0].is_none() {
-            self.inputs[0] = Some(box_type);
-        } else {
-            self.inputs[1] = Some(box_type);
-        }
+    pub fn drop(&mut self, box_type: BoxType, spawned_at: f64) {
+        let idx = if self.inputs[0].is_none() { 0 } else { 1 };
+        self.inputs[idx] = Some(box_type);
+        self.inputs_spawned_at[idx] = spawned_at;
     }
 
     /// Reset the factory, setting all its inputs to 0
@@ -60,7 +66,7 @@ pub fn add_item_to_factory(
     mut incorrect_recipe_events: EventWriter<OnIncorrectFactoryRecipe>,
 ) {
     for drop_event in drop_events.iter() {
-        factory.drop(drop_event.box_type);
+        factory.drop(drop_event.box_type, drop_event.spawned_at);
 
         if !factory.has_completed_recipe() {
             return;
@@ -84,6 +90,7 @@ pub fn add_item_to_factory(
                 factory.inputs[0].unwrap(),
                 factory.inputs[1].unwrap(),
                 FACTORY_OUTPUT_LOCATION,
+                factory.inputs_spawned_at,
             ));
         };
 
@@ -102,12 +109,14 @@ pub fn start_factory_production(
     info!("Started production of {:?}", factory.output_queue[0]);
     start_production_events.send(OnFactoryStartProducing {
         box_type: factory.output_queue[0],
+        elapsed: Duration::ZERO,
     });
     factory.is_producing = true;
 }
 
 pub fn finish_factory_production(
     mut commands: Commands,
+    time: Res<GameClock>,
     textures: Res<TextureAssets>,
     mut factory: ResMut<Factory>,
     mut produced_events: EventWriter<OnFactoryFinishProducing>,
@@ -135,8 +144,10 @@ pub fn finish_factory_production(
                 &mut commands,
                 sprite,
                 built,
+                time.seconds_since_startup(),
                 FACTORY_OUTPUT_INITIAL_VELOCITY,
             );
+            commands.entity(sprite).insert(LandingFromFactory);
         }
     }
 }
@@ -147,13 +158,16 @@ pub fn handle_production_started(
     mut started_events: EventReader<OnFactoryStartProducing>,
 ) {
     for evt in started_events.iter() {
+        let mut timer = new_timer();
+        timer.0.set_elapsed(evt.elapsed);
+
         commands
             .spawn_bundle(SpriteBundle {
                 texture: textures.countdown[9].clone(),
                 transform: Transform::from_translation(FACTORY_OUTPUT_INDICATOR_LOCATION),
                 ..default()
             })
-            .insert(new_timer())
+            .insert(timer)
             .insert(FactoryProductionIndicator)
             .with_children(|children| {
                 children.spawn_bundle(SpriteBundle {
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::demand::{
+    DemandGenerator, DifficultyScaledDemands, RecipeAwareDemands, WeightedTableDemands,
+};
+
+/// A resource holding the rules the next game will be played with, selected from the menu
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Ships demand more as time goes on, but only goods the factory can make
+    #[default]
+    Standard,
+
+    /// The original rules - ships demand anything from a fixed table
+    Classic,
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Standard => "Standard",
+            GameMode::Classic => "Classic",
+        }
+    }
+
+    /// The game mode that follows this one when cycling through modes on the menu
+    pub fn next(&self) -> Self {
+        match self {
+            GameMode::Standard => GameMode::Classic,
+            GameMode::Classic => GameMode::Standard,
+        }
+    }
+
+    /// Creates the demand generator used to decide what ships want in this game mode
+    pub fn demand_generator(&self) -> Box<dyn DemandGenerator> {
+        match self {
+            GameMode::Standard => Box::new(RecipeAwareDemands(Box::new(
+                DifficultyScaledDemands::default(),
+            ))),
+            GameMode::Classic => Box::new(WeightedTableDemands::default()),
+        }
+    }
+}
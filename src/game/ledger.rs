@@ -0,0 +1,30 @@
+use chrono::NaiveDate;
+
+#[derive(Clone, Debug)]
+pub struct LedgerEntry {
+    pub date: NaiveDate,
+    pub description: String,
+
+    /// The change in the player's score, negative for losses
+    pub amount: f32,
+}
+
+/// A resource that keeps a record of notable gains and losses over the current game
+#[derive(Default, Debug)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    pub fn record(&mut self, date: NaiveDate, description: impl Into<String>, amount: f32) {
+        self.entries.push(LedgerEntry {
+            date,
+            description: description.into(),
+            amount,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
@@ -1,20 +1,33 @@
 pub mod actions;
 mod animation;
+pub mod calendar;
+pub mod clock;
 pub mod components;
+pub mod conveyor;
+pub mod crane;
 mod day_night_cycle;
+pub mod demand;
 mod spawners;
 
 #[cfg(feature = "debug_system")]
 mod debug;
 
 pub mod factory;
+pub mod game_mode;
+pub mod ledger;
+pub mod night;
 pub mod rng;
+pub mod save;
+pub mod seasons;
 mod ui;
+pub mod warehouse;
+pub mod weather;
+pub mod wind;
 
 pub use animation::{Animation, AnimationState};
-pub use day_night_cycle::{OnRainEnd, OnRainStart};
-pub use spawners::OnShipSpawned;
+pub use spawners::{OnShipFoundered, OnShipSpawned};
 pub use ui::OnCoinsReceived;
+pub use weather::{OnRainEnd, OnRainStart};
 
 use bevy::prelude::*;
 use heron::prelude::*;
@@ -23,11 +36,23 @@ use iyes_loopless::prelude::AppLooplessStateExt;
 use crate::{
     game::{
         actions::ActionPlugin,
-        components::{FactoryGraphic, FactoryInput, HardSurface, SplashCatcher, WorldEntity},
+        calendar::CalendarPlugin,
+        clock::GameClockPlugin,
+        components::{
+            Background, FactoryGraphic, FactoryInput, HardSurface, SplashCatcher, WarehouseFloor,
+            WorldEntity,
+        },
+        conveyor::ConveyorPlugin,
+        crane::CranePlugin,
         day_night_cycle::DayNightCyclePlugin,
         factory::FactoryPlugin,
+        night::NightPlugin,
+        save::SavePlugin,
         spawners::{spawn_torch, GamePhysicsLayer, SpawningPlugin},
         ui::UiPlugin,
+        warehouse::{WarehousePlugin, WAREHOUSE_HALF_EXTENTS},
+        weather::WeatherPlugin,
+        wind::WindPlugin,
     },
     loader::{AnimationAssets, TextureAssets},
     GameState, GRID_SIZE, HEIGHT, WIDTH,
@@ -38,7 +63,10 @@ use crate::game::debug::DebugPlugin;
 
 use animation::AnimationPlugin;
 
-use self::{components::TutorialMarker, ui::Score};
+use self::{components::TutorialMarker, game_mode::GameMode, ledger::Ledger, ui::Score};
+
+/// The centre of the warehouse floor that crates can be stacked on
+pub const WAREHOUSE_LOCATION: Vec3 = Vec3::new(-0.193 * WIDTH, -1.75 * GRID_SIZE, 0.0);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemLabel)]
 pub enum SystemLabels {
@@ -52,16 +80,25 @@ pub struct GamePlugin;
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         info!("Mounting GamePlugin");
-        app.add_event::<OnRainStart>()
-            .add_event::<OnRainEnd>()
-            .add_plugin(PhysicsPlugin::default()) // Add the plugin
+        app.add_plugin(PhysicsPlugin::default()) // Add the plugin
             .insert_resource(Gravity::from(Vec3::new(0.0, -500.0, 0.0)))
+            .insert_resource(Ledger::default())
+            .insert_resource(GameMode::default())
+            .add_plugin(GameClockPlugin)
             .add_plugin(AnimationPlugin)
             .add_plugin(ActionPlugin)
             .add_plugin(DayNightCyclePlugin)
             .add_plugin(UiPlugin)
             .add_plugin(SpawningPlugin)
             .add_plugin(FactoryPlugin)
+            .add_plugin(WarehousePlugin)
+            .add_plugin(ConveyorPlugin)
+            .add_plugin(CranePlugin)
+            .add_plugin(WindPlugin)
+            .add_plugin(WeatherPlugin)
+            .add_plugin(CalendarPlugin)
+            .add_plugin(NightPlugin)
+            .add_plugin(SavePlugin)
             .add_enter_system(GameState::Playing, setup_world)
             .add_exit_system(GameState::Playing, teardown_world);
 
@@ -76,15 +113,20 @@ fn setup_world(
     mut commands: Commands,
     textures: Res<TextureAssets>,
     animations: Res<AnimationAssets>,
+    mut ledger: ResMut<Ledger>,
 ) {
     info!("Setting up game world");
 
+    // the ledger is kept after teardown so it can be shown on the game over screen
+    ledger.clear();
+
     /* BACKGROUNDS */
     commands
         .spawn_bundle(SpriteBundle {
             texture: textures.background.clone(),
             ..Default::default()
         })
+        .insert(Background)
         .insert(WorldEntity);
 
     /* TORCHES */
@@ -106,7 +148,7 @@ fn setup_world(
         .spawn_bundle((
             RigidBody::Static,
             CollisionShape::Cuboid {
-                half_extends: Vec3::new(103.0, 26.0, GRID_SIZE / 2.0),
+                half_extends: WAREHOUSE_HALF_EXTENTS.extend(GRID_SIZE / 2.0),
                 border_radius: None,
             },
             CollisionLayers::none()
@@ -115,11 +157,12 @@ fn setup_world(
         ))
         .insert_bundle(SpriteBundle::default())
         .insert_bundle(TransformBundle {
-            local: Transform::from_xyz(-0.193 * WIDTH, -1.75 * GRID_SIZE, 0.0),
+            local: Transform::from_translation(WAREHOUSE_LOCATION),
             ..default()
         })
         .insert(WorldEntity)
-        .insert(HardSurface);
+        .insert(HardSurface)
+        .insert(WarehouseFloor);
 
     /* FACTORY */
     commands
@@ -0,0 +1,268 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use iyes_loopless::prelude::{AppLooplessStateExt, IntoConditionalSystem};
+
+use crate::{
+    game::{
+        clock::GameClock,
+        components::{
+            CountDownTimer, DarknessOverlay, FactoryProductionIndicator, NightWork, Torch,
+            WorldEntity,
+        },
+        day_night_cycle::{TimeOfDay, TORCH_THRESHOLD},
+        ui::Score,
+    },
+    GameState, GRID_SIZE, HEIGHT, WIDTH,
+};
+
+/// What it costs each night to keep the carts running after dark
+pub const NIGHT_SHIFT_COST: f32 = 15.0;
+
+/// What it costs each night to light the factory
+pub const FACTORY_LAMPS_COST: f32 = 10.0;
+
+/// How fast the factory works at night without lamps, as a proportion of its usual speed
+const UNLIT_FACTORY_SPEED: f32 = 0.5;
+
+/// Where the factory lamps light up, when they are paid for
+const FACTORY_LAMP_LOCATION: Vec2 = Vec2::new(0.0, 1.5 * GRID_SIZE);
+
+/// How far the light from a torch or lamp reaches
+const LIGHT_RADIUS: f32 = 3.0 * GRID_SIZE;
+
+/// The darkness overlay is drawn at a lower resolution than the screen, and stretched to fit
+const OVERLAY_PIXEL_SIZE: f32 = 8.0;
+const OVERLAY_Z: f32 = 20.0;
+
+/// How dark the overlay gets in the middle of the night
+const MAX_DARKNESS: f32 = 0.7;
+
+/// Event triggered at nightfall for the night work the company is paying for
+pub struct OnNightWorkPaid {
+    pub description: &'static str,
+    pub cost: f32,
+}
+
+/// A resource holding the night work the player has chosen to pay for
+#[derive(Default, Debug)]
+pub struct NightShift {
+    /// Carts keep delivering after dark
+    pub carts: bool,
+
+    /// The factory is lit and works at full speed after dark
+    pub factory_lamps: bool,
+
+    /// Whether the night carts have been paid for tonight
+    pub carts_paid: bool,
+
+    /// Whether the factory lamps have been paid for tonight
+    pub factory_lamps_paid: bool,
+}
+
+impl NightShift {
+    /// What the given night work costs for a night
+    pub fn cost(work: NightWork) -> f32 {
+        match work {
+            NightWork::Carts => NIGHT_SHIFT_COST,
+            NightWork::FactoryLamps => FACTORY_LAMPS_COST,
+        }
+    }
+
+    pub fn is_hired(&self, work: NightWork) -> bool {
+        match work {
+            NightWork::Carts => self.carts,
+            NightWork::FactoryLamps => self.factory_lamps,
+        }
+    }
+
+    pub fn set_hired(&mut self, work: NightWork, hired: bool) {
+        match work {
+            NightWork::Carts => self.carts = hired,
+            NightWork::FactoryLamps => self.factory_lamps = hired,
+        }
+    }
+
+    fn is_paid(&self, work: NightWork) -> bool {
+        match work {
+            NightWork::Carts => self.carts_paid,
+            NightWork::FactoryLamps => self.factory_lamps_paid,
+        }
+    }
+
+    fn set_paid(&mut self, work: NightWork, paid: bool) {
+        match work {
+            NightWork::Carts => self.carts_paid = paid,
+            NightWork::FactoryLamps => self.factory_lamps_paid = paid,
+        }
+    }
+}
+
+pub struct NightPlugin;
+
+impl Plugin for NightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NightShift>()
+            .add_event::<OnNightWorkPaid>()
+            .add_enter_system(GameState::Playing, reset_night_shift)
+            .add_enter_system(GameState::Playing, spawn_darkness_overlay)
+            .add_system(pay_for_night_work.run_in_state(GameState::Playing))
+            .add_system(slow_unlit_factory.run_in_state(GameState::Playing))
+            .add_system(update_darkness_overlay.run_in_state(GameState::Playing));
+    }
+}
+
+fn reset_night_shift(mut night_shift: ResMut<NightShift>) {
+    *night_shift = NightShift::default();
+}
+
+/// Charges once a night for each kind of night work in use, either as night falls or when
+/// it is started during the night. Night work the company can't afford is stopped.
+fn pay_for_night_work(
+    time_of_day: Res<TimeOfDay>,
+    score: Res<Score>,
+    mut night_shift: ResMut<NightShift>,
+    mut paid_events: EventWriter<OnNightWorkPaid>,
+) {
+    let works = [NightWork::Carts, NightWork::FactoryLamps];
+
+    if !time_of_day.is_night() {
+        // a new night's work needs paying for again
+        for work in works {
+            if night_shift.is_paid(work) {
+                night_shift.set_paid(work, false);
+            }
+        }
+        return;
+    }
+
+    let mut funds = score.0;
+
+    for work in works {
+        if !night_shift.is_hired(work) || night_shift.is_paid(work) {
+            continue;
+        }
+
+        let cost = NightShift::cost(work);
+        if funds < cost {
+            info!("Can't afford {:?} tonight, stopping it", work);
+            night_shift.set_hired(work, false);
+            continue;
+        }
+
+        funds -= cost;
+        night_shift.set_paid(work, true);
+        paid_events.send(OnNightWorkPaid {
+            description: match work {
+                NightWork::Carts => "Night shift for the carts",
+                NightWork::FactoryLamps => "Lamp oil for the factory",
+            },
+            cost,
+        });
+    }
+}
+
+/// Slows the factory down in the dark, by holding back its production timer
+fn slow_unlit_factory(
+    time: Res<GameClock>,
+    time_of_day: Res<TimeOfDay>,
+    night_shift: Res<NightShift>,
+    mut production: Query<&mut CountDownTimer, With<FactoryProductionIndicator>>,
+) {
+    if !time_of_day.is_night() || night_shift.factory_lamps {
+        return;
+    }
+
+    let held_back = time.delta().mul_f32(1.0 - UNLIT_FACTORY_SPEED);
+
+    for mut timer in production.iter_mut() {
+        if !timer.0.finished() {
+            let elapsed = timer.0.elapsed().saturating_sub(held_back);
+            timer.0.set_elapsed(elapsed);
+        }
+    }
+}
+
+fn spawn_darkness_overlay(mut commands: Commands) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::NONE,
+                custom_size: Some(Vec2::new(WIDTH, HEIGHT)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, OVERLAY_Z),
+            ..default()
+        })
+        .insert(DarknessOverlay::default())
+        .insert(WorldEntity);
+}
+
+/// Builds a darkness mask, which is opaque except for pools of light around each light
+fn darkness_mask(lights: &[Vec2]) -> Image {
+    let width = (WIDTH / OVERLAY_PIXEL_SIZE) as u32;
+    let height = (HEIGHT / OVERLAY_PIXEL_SIZE) as u32;
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+
+    for row in 0..height {
+        for col in 0..width {
+            let position = Vec2::new(
+                (col as f32 + 0.5) * OVERLAY_PIXEL_SIZE - WIDTH / 2.0,
+                HEIGHT / 2.0 - (row as f32 + 0.5) * OVERLAY_PIXEL_SIZE,
+            );
+
+            let lit = lights
+                .iter()
+                .map(|light| 1.0 - (position.distance(*light) / LIGHT_RADIUS).clamp(0.0, 1.0))
+                .fold(0.0, f32::max);
+
+            let alpha = 1.0 - lit * lit;
+            data.extend_from_slice(&[255, 255, 255, (alpha * 255.0) as u8]);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+/// Darkens the docks as the sky darkens, leaving pools of light around the torches and the
+/// factory lamps so crates are easier to see there
+fn update_darkness_overlay(
+    clear_colour: Res<ClearColor>,
+    night_shift: Res<NightShift>,
+    mut images: ResMut<Assets<Image>>,
+    torches: Query<&GlobalTransform, With<Torch>>,
+    mut overlays: Query<(&mut DarknessOverlay, &mut Sprite, &mut Handle<Image>)>,
+) {
+    let mut lights = torches
+        .iter()
+        .map(|tx| tx.translation().truncate())
+        .collect::<Vec<_>>();
+    if night_shift.factory_lamps {
+        lights.push(FACTORY_LAMP_LOCATION);
+    }
+
+    let brightness = clear_colour.0.r() + clear_colour.0.g() + clear_colour.0.b();
+    let darkness = ((TORCH_THRESHOLD - brightness) / TORCH_THRESHOLD).clamp(0.0, 1.0);
+    let colour = Color::rgba(0.02, 0.02, 0.08, darkness * MAX_DARKNESS);
+
+    for (mut overlay, mut sprite, mut texture) in overlays.iter_mut() {
+        if overlay.lights != lights {
+            *texture = images.add(darkness_mask(&lights));
+            overlay.lights = lights.clone();
+        }
+
+        if sprite.color != colour {
+            sprite.color = colour;
+        }
+    }
+}
@@ -1,20 +1,45 @@
 use std::ops::Range;
 
 use rand::{rngs::ThreadRng, Rng};
+use serde::{Deserialize, Serialize};
 
+// reduce the spawn gap by 0.05 / second (every 20 seconds reduce gap by 1s), up to 8s
+const SPEED_UP_RATE_PER_SECOND: f64 = 0.05;
+const MIN_SPAWN_RANGE_START: f64 = 8.0;
+const MIN_SPAWN_RANGE_END: f64 = 9.0;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RandomSpawnTimer {
     pub spawn_range: Range<f64>,
     next_spawn: f64,
 }
 
 impl RandomSpawnTimer {
-    pub fn tick(&mut self, rng: &mut ThreadRng, current_time: f64) -> bool {
-        if self.next_spawn < current_time {
-            self.next_spawn = current_time + rng.gen_range(self.spawn_range.clone());
-            true
-        } else {
-            false
+    /// Restarts the timer so that the next item spawns at the given time
+    pub fn restart_at(&mut self, time: f64) {
+        self.next_spawn = time;
+    }
+
+    /// Returns the time of the next spawn and schedules the one after it, speeding up
+    /// spawns for every second that passes between the two
+    pub fn advance(&mut self, rng: &mut ThreadRng) -> f64 {
+        let spawn_time = self.next_spawn;
+        let gap = rng.gen_range(self.spawn_range.clone());
+
+        for _ in 0..(gap.floor() as usize) {
+            self.speed_up();
         }
+
+        self.next_spawn += gap;
+        spawn_time
+    }
+
+    fn speed_up(&mut self) {
+        let new_min =
+            (self.spawn_range.start - SPEED_UP_RATE_PER_SECOND).max(MIN_SPAWN_RANGE_START);
+        let new_max = (new_min + (self.spawn_range.end - self.spawn_range.start) - 0.01)
+            .max(MIN_SPAWN_RANGE_END);
+        self.spawn_range = new_min..new_max;
     }
 }
 
@@ -0,0 +1,688 @@
+use std::time::Duration;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use chrono::NaiveDate;
+use heron::{AxisAngle, Velocity};
+use iyes_loopless::{
+    prelude::{AppLooplessStateExt, ConditionHelpers, IntoConditionalSystem},
+    state::NextState,
+};
+use leafwing_input_manager::prelude::ActionState;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        actions::{float_crate, DraggingBox},
+        calendar::OnCalendarRestored,
+        clock::GameClock,
+        components::{
+            BoxType, Cart, ConveyorBelt, CountDownTimer, FactoryProductionIndicator, FloatingCrate,
+            InboundCargo, LeavingCart, PhysicsCrate, ShipHold, Sinking, SpawnShipRequest, TopUiBar,
+            Wave, WetCrate, WorldEntity,
+        },
+        conveyor::spawn_conveyor,
+        crane::{spawn_crane, Crane, CRANE_REPAIR_SECONDS, CRANE_RETURN_LOCATION},
+        day_night_cycle::{OnNewDay, TimeOfDay},
+        factory::{events::OnFactoryStartProducing, Factory},
+        game_mode::GameMode,
+        ledger::{Ledger, LedgerEntry},
+        night::NightShift,
+        spawners::{
+            lanes::{SailingDirection, ShipLane},
+            request::spawn_ship_request_icon,
+            schedule::SavedShipSchedule,
+            spawn_physics_crate, spawn_ship_at,
+            suppliers::{SupplierOrder, SUPPLIER_OFFERS},
+            CartSpawningState, ShipSchedule, ShippingLanes, SupplierOrders,
+        },
+        ui::{tutorial::CurrentTutorialLevel, Score},
+        weather::{ActiveWeather, OnWeatherRestored, Weather, WeatherForecast},
+        wind::Wind,
+    },
+    input::PlayerActions,
+    loader::{AnimationAssets, TextureAssets},
+    GameState,
+};
+
+/// The file the game in progress is saved to, or its key in the browser's local storage
+const SAVE_FILE: &str = "the_company.save.yml";
+
+/// Saved games are written to a file next to the game on the desktop
+#[cfg(not(target_arch = "wasm32"))]
+mod storage {
+    use std::{fs, io::ErrorKind};
+
+    use super::SAVE_FILE;
+
+    pub fn exists() -> bool {
+        fs::metadata(SAVE_FILE).is_ok()
+    }
+
+    pub fn read() -> anyhow::Result<String> {
+        Ok(fs::read_to_string(SAVE_FILE)?)
+    }
+
+    pub fn write(contents: &str) -> anyhow::Result<()> {
+        Ok(fs::write(SAVE_FILE, contents)?)
+    }
+
+    pub fn remove() -> anyhow::Result<()> {
+        match fs::remove_file(SAVE_FILE) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Browsers have no file system to write to, so saved games go in local storage on the web
+#[cfg(target_arch = "wasm32")]
+mod storage {
+    use anyhow::anyhow;
+    use web_sys::Storage;
+
+    use super::SAVE_FILE;
+
+    fn local_storage() -> anyhow::Result<Storage> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| anyhow!("local storage is unavailable"))
+    }
+
+    pub fn exists() -> bool {
+        local_storage()
+            .ok()
+            .and_then(|storage| storage.get_item(SAVE_FILE).ok().flatten())
+            .is_some()
+    }
+
+    pub fn read() -> anyhow::Result<String> {
+        local_storage()?
+            .get_item(SAVE_FILE)
+            .map_err(|e| anyhow!("{:?}", e))?
+            .ok_or_else(|| anyhow!("there is no saved game"))
+    }
+
+    pub fn write(contents: &str) -> anyhow::Result<()> {
+        local_storage()?
+            .set_item(SAVE_FILE, contents)
+            .map_err(|e| anyhow!("{:?}", e))
+    }
+
+    pub fn remove() -> anyhow::Result<()> {
+        local_storage()?
+            .remove_item(SAVE_FILE)
+            .map_err(|e| anyhow!("{:?}", e))
+    }
+}
+
+/// Event triggered to save the game in progress, optionally returning to the menu afterwards
+pub struct OnSaveGame {
+    pub quit_to_menu: bool,
+}
+
+/// A resource holding a saved game that is restored on the first frame of play
+pub struct PendingRestore(pub SaveGame);
+
+/// Dates are saved in ISO format, as chrono is built without serde support
+mod iso_date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const FORMAT: &str = "%Y-%m-%d";
+
+    pub fn serialize<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&date.format(FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        NaiveDate::parse_from_str(&value, FORMAT).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedLedgerEntry {
+    #[serde(with = "iso_date")]
+    date: NaiveDate,
+    description: String,
+    amount: f32,
+}
+
+/// A ship waiting in the ship bar to set sail
+#[derive(Serialize, Deserialize)]
+pub struct SavedShipRequest {
+    demands: Vec<BoxType>,
+    imports: Vec<BoxType>,
+    expiry: f32,
+    countdown_elapsed: f32,
+}
+
+/// A ship sailing across the screen
+#[derive(Serialize, Deserialize)]
+pub struct SavedShip {
+    lane: usize,
+    direction: SailingDirection,
+    translation: [f32; 3],
+    demands: Vec<BoxType>,
+    crates: Vec<BoxType>,
+    imports: Vec<BoxType>,
+}
+
+/// A crate being thrown about by the physics simulation
+#[derive(Serialize, Deserialize)]
+pub struct SavedCrate {
+    box_type: BoxType,
+    spawned_at: f64,
+    translation: [f32; 3],
+    rotation: f32,
+    velocity: [f32; 2],
+    angular_velocity: f32,
+    is_wet: bool,
+}
+
+/// A crate drifting on the water
+#[derive(Serialize, Deserialize)]
+pub struct SavedFloatingCrate {
+    box_type: BoxType,
+    spawned_at: f64,
+    translation: [f32; 3],
+    drift: f32,
+    phase: f32,
+    floated_for: f32,
+}
+
+/// A conveyor segment the player has bought
+#[derive(Serialize, Deserialize)]
+pub struct SavedConveyor {
+    translation: [f32; 2],
+    direction: f32,
+}
+
+/// A standing order placed with a supplier, which is matched back up with its offer on restore
+#[derive(Serialize, Deserialize)]
+pub struct SavedSupplierOrder {
+    good: BoxType,
+    due_at: f64,
+    paused_with: Option<f64>,
+}
+
+/// Everything needed to carry on a game in progress
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    seconds_since_startup: f64,
+    pub game_mode: GameMode,
+    pub tutorial_level: u8,
+    score: f32,
+    ledger: Vec<SavedLedgerEntry>,
+
+    time_of_day: f32,
+    #[serde(with = "iso_date")]
+    today: NaiveDate,
+    #[serde(with = "iso_date")]
+    weather_started_on: NaiveDate,
+    active_weather: Weather,
+    wind_velocity: f32,
+    wind_target: f32,
+
+    factory_inputs: [Option<BoxType>; 2],
+    factory_inputs_spawned_at: [f64; 2],
+    factory_queue: Vec<BoxType>,
+
+    /// How far through making the first crate in the queue the factory is, if it has started
+    factory_production_elapsed: Option<f32>,
+
+    /// Crates waiting to be delivered, including those still on carts when the game was saved
+    cart_queue: Vec<BoxType>,
+    cart_patience_seconds: f32,
+    last_cart_spawn: f32,
+    night_shift_carts: bool,
+    factory_lamps: bool,
+    night_shift_carts_paid: bool,
+    factory_lamps_paid: bool,
+    supplier_orders: Vec<SavedSupplierOrder>,
+
+    crane_owned: bool,
+    crane_cycle_elapsed: f32,
+    crane_repair_elapsed: Option<f32>,
+    conveyors: Vec<SavedConveyor>,
+
+    ship_schedule: SavedShipSchedule,
+    ship_requests: Vec<SavedShipRequest>,
+    ships: Vec<SavedShip>,
+    crates: Vec<SavedCrate>,
+    floating_crates: Vec<SavedFloatingCrate>,
+}
+
+/// Whether there is a saved game to continue
+pub fn save_exists() -> bool {
+    storage::exists()
+}
+
+pub fn load_game() -> anyhow::Result<SaveGame> {
+    let contents = storage::read()?;
+    Ok(serde_yaml::from_str(&contents)?)
+}
+
+/// The resources that make up a game session
+#[derive(SystemParam)]
+pub struct Session<'w, 's> {
+    clock: ResMut<'w, GameClock>,
+    game_mode: ResMut<'w, GameMode>,
+    tutorial_level: ResMut<'w, CurrentTutorialLevel>,
+    score: ResMut<'w, Score>,
+    ledger: ResMut<'w, Ledger>,
+    time_of_day: ResMut<'w, TimeOfDay>,
+    forecast: ResMut<'w, WeatherForecast>,
+    active_weather: ResMut<'w, ActiveWeather>,
+    wind: ResMut<'w, Wind>,
+    factory: ResMut<'w, Factory>,
+    cart_spawning: ResMut<'w, CartSpawningState>,
+    schedule: ResMut<'w, ShipSchedule>,
+    dragging: ResMut<'w, DraggingBox>,
+    night_shift: ResMut<'w, NightShift>,
+    supplier_orders: ResMut<'w, SupplierOrders>,
+    crane: ResMut<'w, Crane>,
+    #[system_param(ignore)]
+    _marker: std::marker::PhantomData<&'s ()>,
+}
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<OnSaveGame>()
+            .add_system(save_on_hotkey.run_in_state(GameState::Playing))
+            .add_system(autosave_each_day.run_on_event::<OnNewDay>())
+            .add_system(save_game.run_on_event::<OnSaveGame>())
+            .add_system(
+                restore_saved_game
+                    .run_in_state(GameState::Playing)
+                    .run_if_resource_exists::<PendingRestore>(),
+            )
+            .add_enter_system(GameState::GameOver, delete_saved_game);
+    }
+}
+
+fn save_on_hotkey(
+    action_state_query: Query<&ActionState<PlayerActions>>,
+    mut save_events: EventWriter<OnSaveGame>,
+) {
+    if action_state_query
+        .single()
+        .just_pressed(PlayerActions::SaveAndQuit)
+    {
+        save_events.send(OnSaveGame { quit_to_menu: true });
+    }
+}
+
+/// Saves the game every midnight, so little is lost if the game is closed
+fn autosave_each_day(mut save_events: EventWriter<OnSaveGame>) {
+    save_events.send(OnSaveGame {
+        quit_to_menu: false,
+    });
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn save_game(
+    mut commands: Commands,
+    mut save_events: EventReader<OnSaveGame>,
+    session: Session,
+    requests: Query<(&SpawnShipRequest, &CountDownTimer)>,
+    waves: Query<(&Transform, &ShipLane, &InboundCargo, &Children), (With<Wave>, Without<Sinking>)>,
+    holds: Query<&ShipHold>,
+    crates: Query<(&Transform, &PhysicsCrate, &Velocity, Option<&WetCrate>)>,
+    carts: Query<&Cart, Without<LeavingCart>>,
+    floating_crates: Query<(&Transform, &FloatingCrate)>,
+    conveyors: Query<(&Transform, &ConveyorBelt)>,
+    production: Query<&CountDownTimer, With<FactoryProductionIndicator>>,
+) {
+    let quit_to_menu = save_events.iter().any(|evt| evt.quit_to_menu);
+
+    // crates on carts and in the player's hands go back in the delivery queue
+    let mut cart_queue = carts
+        .iter()
+        .flat_map(|cart| cart.slots.iter().flatten().copied())
+        .collect::<Vec<_>>();
+    cart_queue.extend(session.dragging.box_type);
+    cart_queue.extend(session.cart_spawning.items.iter().copied());
+
+    let ships = waves
+        .iter()
+        .filter_map(|(tx, ship_lane, cargo, children)| {
+            let hold = children.iter().find_map(|child| holds.get(*child).ok())?;
+
+            Some(SavedShip {
+                lane: ship_lane.lane,
+                direction: ship_lane.direction,
+                translation: tx.translation.to_array(),
+                demands: hold.demands.clone(),
+                crates: hold.crates.clone(),
+                imports: cargo.0.clone(),
+            })
+        })
+        .collect();
+
+    let mut saved_crates = crates
+        .iter()
+        .map(|(tx, physics_crate, velocity, wet)| SavedCrate {
+            box_type: physics_crate.box_type,
+            spawned_at: physics_crate.spawned_at,
+            translation: tx.translation.to_array(),
+            rotation: tx.rotation.to_euler(EulerRot::XYZ).2,
+            velocity: velocity.linear.truncate().to_array(),
+            angular_velocity: Vec3::from(velocity.angular).z,
+            is_wet: wet.is_some(),
+        })
+        .collect::<Vec<_>>();
+
+    // a crate swinging on the crane is put back in the warehouse
+    if let Some(load) = session.crane.load.as_ref() {
+        saved_crates.push(SavedCrate {
+            box_type: load.box_type,
+            spawned_at: load.spawned_at,
+            translation: CRANE_RETURN_LOCATION.to_array(),
+            rotation: 0.0,
+            velocity: [0.0, 0.0],
+            angular_velocity: 0.0,
+            is_wet: false,
+        });
+    }
+
+    let save = SaveGame {
+        seconds_since_startup: session.clock.seconds_since_startup(),
+        game_mode: *session.game_mode,
+        tutorial_level: session.tutorial_level.0,
+        score: session.score.0,
+        ledger: session
+            .ledger
+            .entries
+            .iter()
+            .map(|entry| SavedLedgerEntry {
+                date: entry.date,
+                description: entry.description.clone(),
+                amount: entry.amount,
+            })
+            .collect(),
+        time_of_day: session.time_of_day.time_of_day,
+        today: session.time_of_day.today,
+        weather_started_on: session.forecast.started_on,
+        active_weather: session.active_weather.0,
+        wind_velocity: session.wind.velocity,
+        wind_target: session.wind.target,
+        factory_inputs: session.factory.inputs,
+        factory_inputs_spawned_at: session.factory.inputs_spawned_at,
+        factory_queue: session.factory.output_queue.clone(),
+        factory_production_elapsed: production.iter().next().map(|timer| timer.0.elapsed_secs()),
+        cart_queue,
+        cart_patience_seconds: session.cart_spawning.patience_seconds,
+        last_cart_spawn: session.cart_spawning.last_spawn,
+        night_shift_carts: session.night_shift.carts,
+        factory_lamps: session.night_shift.factory_lamps,
+        night_shift_carts_paid: session.night_shift.carts_paid,
+        factory_lamps_paid: session.night_shift.factory_lamps_paid,
+        supplier_orders: session
+            .supplier_orders
+            .orders
+            .iter()
+            .map(|order| SavedSupplierOrder {
+                good: order.offer.good,
+                due_at: order.due_at,
+                paused_with: order.paused_with,
+            })
+            .collect(),
+        crane_owned: session.crane.owned,
+        crane_cycle_elapsed: session.crane.cycle.elapsed_secs(),
+        crane_repair_elapsed: session.crane.repair.as_ref().map(Timer::elapsed_secs),
+        conveyors: conveyors
+            .iter()
+            .map(|(tx, conveyor)| SavedConveyor {
+                translation: tx.translation.truncate().to_array(),
+                direction: conveyor.direction,
+            })
+            .collect(),
+        ship_schedule: session.schedule.to_saved(),
+        ship_requests: requests
+            .iter()
+            .map(|(request, timer)| SavedShipRequest {
+                demands: request.demands.clone(),
+                imports: request.imports.clone(),
+                expiry: request.expiry,
+                countdown_elapsed: timer.0.elapsed_secs(),
+            })
+            .collect(),
+        ships,
+        crates: saved_crates,
+        floating_crates: floating_crates
+            .iter()
+            .map(|(tx, floating)| SavedFloatingCrate {
+                box_type: floating.box_type,
+                spawned_at: floating.spawned_at,
+                translation: tx.translation.to_array(),
+                drift: floating.drift,
+                phase: floating.phase,
+                floated_for: floating.timer.elapsed_secs(),
+            })
+            .collect(),
+    };
+
+    match serde_yaml::to_string(&save)
+        .map_err(anyhow::Error::from)
+        .and_then(|contents| storage::write(&contents))
+    {
+        Ok(_) => info!("Saved game to {}", SAVE_FILE),
+        Err(e) => warn!("Unable to save game: {}", e),
+    }
+
+    if quit_to_menu {
+        commands.insert_resource(NextState(GameState::Menu));
+    }
+}
+
+/// Puts a saved game back together, once the world has been set up for a new game
+#[allow(clippy::too_many_arguments)]
+fn restore_saved_game(
+    mut commands: Commands,
+    pending: Res<PendingRestore>,
+    mut session: Session,
+    textures: Res<TextureAssets>,
+    animations: Res<AnimationAssets>,
+    lanes: Res<ShippingLanes>,
+    top_bar_query: Query<Entity, With<TopUiBar>>,
+    mut production_events: EventWriter<OnFactoryStartProducing>,
+    mut weather_events: EventWriter<OnWeatherRestored>,
+    mut calendar_events: EventWriter<OnCalendarRestored>,
+) {
+    let save = &pending.0;
+    info!("Restoring saved game from {}", save.today);
+    commands.remove_resource::<PendingRestore>();
+
+    session
+        .clock
+        .set_seconds_since_startup(save.seconds_since_startup);
+    *session.game_mode = save.game_mode;
+    session.tutorial_level.0 = save.tutorial_level;
+    session.score.0 = save.score;
+    session.ledger.entries = save
+        .ledger
+        .iter()
+        .map(|entry| LedgerEntry {
+            date: entry.date,
+            description: entry.description.clone(),
+            amount: entry.amount,
+        })
+        .collect();
+
+    session.time_of_day.time_of_day = save.time_of_day;
+    session.time_of_day.today = save.today;
+    *session.forecast = WeatherForecast::restore(save.weather_started_on, save.today);
+    // the weather carries on as it was, rather than arriving again and whipping up a new storm
+    session.active_weather.0 = save.active_weather;
+    session.wind.velocity = save.wind_velocity;
+    session.wind.target = save.wind_target;
+    weather_events.send(OnWeatherRestored(save.active_weather));
+
+    session.factory.inputs = save.factory_inputs;
+    session.factory.inputs_spawned_at = save.factory_inputs_spawned_at;
+    session.factory.output_queue = save.factory_queue.clone();
+    // production carries on where it left off, rather than starting the crate again
+    session.factory.is_producing = false;
+    if let (Some(elapsed), Some(box_type)) =
+        (save.factory_production_elapsed, save.factory_queue.first())
+    {
+        session.factory.is_producing = true;
+        production_events.send(OnFactoryStartProducing {
+            box_type: *box_type,
+            elapsed: Duration::from_secs_f32(elapsed),
+        });
+    }
+
+    session.cart_spawning.items.clear();
+    session
+        .cart_spawning
+        .items
+        .extend(save.cart_queue.iter().copied());
+    session.cart_spawning.patience_seconds = save.cart_patience_seconds;
+    session.cart_spawning.last_spawn = save.last_cart_spawn;
+    session.night_shift.carts = save.night_shift_carts;
+    session.night_shift.factory_lamps = save.factory_lamps;
+    session.night_shift.carts_paid = save.night_shift_carts_paid;
+    session.night_shift.factory_lamps_paid = save.factory_lamps_paid;
+    session.supplier_orders.orders = save
+        .supplier_orders
+        .iter()
+        .filter_map(|saved| {
+            let offer = SUPPLIER_OFFERS
+                .iter()
+                .find(|offer| offer.good == saved.good)?;
+
+            Some(SupplierOrder {
+                offer: *offer,
+                due_at: saved.due_at,
+                paused_with: saved.paused_with,
+            })
+        })
+        .collect();
+
+    session.crane.owned = save.crane_owned;
+    session
+        .crane
+        .cycle
+        .set_elapsed(Duration::from_secs_f32(save.crane_cycle_elapsed));
+    session.crane.repair = save.crane_repair_elapsed.map(|elapsed| {
+        let mut repair = Timer::from_seconds(CRANE_REPAIR_SECONDS, false);
+        repair.set_elapsed(Duration::from_secs_f32(elapsed));
+        repair
+    });
+    if save.crane_owned {
+        spawn_crane(&mut commands, save.crane_repair_elapsed.is_some());
+    }
+
+    for conveyor in save.conveyors.iter() {
+        spawn_conveyor(
+            &mut commands,
+            &textures,
+            Vec2::from(conveyor.translation),
+            conveyor.direction,
+        );
+    }
+
+    *session.schedule = ShipSchedule::from_saved(save.game_mode, save.ship_schedule.clone());
+    *session.dragging = DraggingBox::default();
+
+    if let Ok(top_bar) = top_bar_query.get_single() {
+        commands.entity(top_bar).with_children(|layout| {
+            for request in save.ship_requests.iter() {
+                spawn_ship_request_icon(
+                    layout,
+                    &textures,
+                    request.demands.clone(),
+                    request.imports.clone(),
+                    request.expiry,
+                    Duration::from_secs_f32(request.countdown_elapsed),
+                );
+            }
+        });
+    }
+
+    for ship in save.ships.iter() {
+        spawn_ship_at(
+            &mut commands,
+            &textures,
+            &animations,
+            &lanes,
+            ShipLane {
+                lane: ship.lane,
+                direction: ship.direction,
+            },
+            SpawnShipRequest {
+                demands: ship.demands.clone(),
+                imports: ship.imports.clone(),
+                expiry: 0.0,
+            },
+            Vec3::from(ship.translation),
+            ship.crates.clone(),
+        );
+    }
+
+    for saved in save.crates.iter() {
+        let sprite = commands
+            .spawn_bundle(SpriteBundle {
+                texture: saved.box_type.get_image(&textures),
+                transform: Transform::from_translation(Vec3::from(saved.translation))
+                    .with_rotation(Quat::from_rotation_z(saved.rotation)),
+                ..default()
+            })
+            .id();
+
+        let velocity = Vec2::from(saved.velocity);
+        spawn_physics_crate(
+            &mut commands,
+            sprite,
+            saved.box_type,
+            saved.spawned_at,
+            velocity,
+        );
+
+        commands.entity(sprite).insert(
+            Velocity::from_linear(velocity.extend(0.0))
+                .with_angular(AxisAngle::new(Vec3::Z, saved.angular_velocity)),
+        );
+
+        if saved.is_wet {
+            commands.entity(sprite).insert(WetCrate);
+        }
+    }
+
+    for saved in save.floating_crates.iter() {
+        let sprite = commands
+            .spawn_bundle(SpriteBundle {
+                texture: saved.box_type.get_image(&textures),
+                transform: Transform::from_translation(Vec3::from(saved.translation)),
+                ..default()
+            })
+            .insert(WorldEntity)
+            .id();
+
+        float_crate(
+            &mut commands,
+            sprite,
+            saved.box_type,
+            saved.spawned_at,
+            saved.drift,
+            saved.phase,
+            Duration::from_secs_f32(saved.floated_for),
+        );
+    }
+
+    // lets the calendar catch up with the restored date. This isn't a new day, which would
+    // autosave before the restored world has been spawned
+    calendar_events.send(OnCalendarRestored(save.today));
+}
+
+/// The company has gone under, so there is nothing left to continue
+fn delete_saved_game() {
+    match storage::remove() {
+        Ok(_) => info!("Deleted saved game"),
+        Err(e) => warn!("Unable to delete saved game: {}", e),
+    }
+}
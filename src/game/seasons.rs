@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use chrono::{Datelike, NaiveDate};
+
+use crate::game::weather::Weather;
+
+/// The hours of daylight on the shortest and longest days of the year
+const SHORTEST_DAY_HOURS: f32 = 9.0;
+const LONGEST_DAY_HOURS: f32 = 15.0;
+
+/// The day of the year with the most daylight
+const MIDSUMMER_DAY: f32 = 172.0;
+
+/// The hour the sun is highest in the sky
+const SOLAR_NOON: f32 = 12.5;
+
+/// The time of year, which changes the length of the day, the colour of the sky and
+/// how likely bad weather is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn from_date(date: NaiveDate) -> Self {
+        match date.month() {
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            9..=11 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    /// The chance of each kind of bad weather on a day in this season. Any remaining chance
+    /// is fine weather. Heatwaves only happen in summer and snow only falls in winter.
+    pub fn weather_chances(&self) -> &'static [(Weather, f64)] {
+        match self {
+            Season::Spring => &[(Weather::Stormy, 0.2), (Weather::Foggy, 0.1)],
+            Season::Summer => &[
+                (Weather::Stormy, 0.15),
+                (Weather::Foggy, 0.05),
+                (Weather::Heatwave, 0.15),
+            ],
+            Season::Autumn => &[(Weather::Stormy, 0.25), (Weather::Foggy, 0.2)],
+            Season::Winter => &[
+                (Weather::Stormy, 0.1),
+                (Weather::Foggy, 0.1),
+                (Weather::Snowy, 0.25),
+            ],
+        }
+    }
+
+    /// Multiplied with the sky palette to give each season its own light
+    pub fn sky_tint(&self) -> Vec3 {
+        match self {
+            Season::Spring => Vec3::new(0.95, 1.05, 0.95),
+            Season::Summer => Vec3::new(1.05, 1.0, 0.95),
+            Season::Autumn => Vec3::new(1.05, 0.95, 0.85),
+            Season::Winter => Vec3::new(0.9, 0.95, 1.05),
+        }
+    }
+
+    /// The tint applied to the dock background
+    pub fn background_tint(&self) -> Color {
+        match self {
+            Season::Spring => Color::rgb(0.95, 1.0, 0.95),
+            Season::Summer => Color::WHITE,
+            Season::Autumn => Color::rgb(1.0, 0.92, 0.85),
+            Season::Winter => Color::rgb(0.85, 0.9, 1.0),
+        }
+    }
+}
+
+/// The number of hours of daylight on the given date
+pub fn day_length(date: NaiveDate) -> f32 {
+    let angle = std::f32::consts::TAU * (date.ordinal() as f32 - MIDSUMMER_DAY) / 365.0;
+    let mid = 0.5 * (LONGEST_DAY_HOURS + SHORTEST_DAY_HOURS);
+    let range = 0.5 * (LONGEST_DAY_HOURS - SHORTEST_DAY_HOURS);
+
+    mid + range * angle.cos()
+}
+
+/// The hour the sun rises on the given date
+pub fn sunrise(date: NaiveDate) -> f32 {
+    SOLAR_NOON - 0.5 * day_length(date)
+}
+
+/// The hour the sun sets on the given date
+pub fn sunset(date: NaiveDate) -> f32 {
+    SOLAR_NOON + 0.5 * day_length(date)
+}
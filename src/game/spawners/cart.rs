@@ -1,9 +1,18 @@
+use std::ops::Deref;
+
 use bevy::prelude::*;
 
 use crate::{
     game::{
-        components::{AnimateWithSpeed, BoxType, Cart, CartCrate, WorldEntity},
-        // ui::CurrentTutorialLevel,
+        clock::GameClock,
+        components::{
+            AnimateWithSpeed, BoxType, Cart, CartCrate, CartPatience, CartPatienceMeter,
+            LeavingCart, PickArea, Pickable, WorldEntity,
+        },
+        day_night_cycle::TimeOfDay,
+        night::NightShift,
+        spawners::cart_types::{CartType, CartTypeDefinition, CartTypes},
+        ui::tutorial::{CurrentTutorialLevel, TUTORIAL_COMPLETE_LEVEL},
         AnimationState,
     },
     loader::{AnimationAssets, TextureAssets},
@@ -12,121 +21,367 @@ use crate::{
 
 pub struct OnCartSpawned;
 
+/// Event triggered when a cart leaves the dock, with the number of crates nobody collected
+pub struct OnCartLeft {
+    pub unclaimed_crates: usize,
+}
+
 pub const CART_SPAWN_DELAY: f32 = 5.0;
 pub const CART_Z_POS: f32 = 0.4;
 
+/// The x position carts spawn at, off the right hand side of the screen
+pub const CART_SPAWN_X: f32 = WIDTH / 2.0 + GRID_SIZE * 5.0;
+const CART_CRATE_SCALE: f32 = 0.75;
+
+/// The x positions of the loading bays carts wait in, from left to right
+pub const LOADING_BAYS_X: [f32; 4] = [-11.0 * GRID_SIZE, -5.5 * GRID_SIZE, 0.0, 5.5 * GRID_SIZE];
+
+const PATIENCE_METER_WIDTH: f32 = 2.0 * GRID_SIZE;
+const PATIENCE_METER_HEIGHT: f32 = 4.0;
+
+/// The goods waiting to be delivered by cart, in order. Each queued item keeps the same id
+/// while the queue is edited, so the queue UI can follow an item as it is dragged about.
+#[derive(Debug, Default)]
+pub struct CartQueue {
+    items: Vec<BoxType>,
+    ids: Vec<u64>,
+    next_id: u64,
+}
+
+impl Deref for CartQueue {
+    type Target = [BoxType];
+
+    fn deref(&self) -> &[BoxType] {
+        &self.items
+    }
+}
+
+impl Extend<BoxType> for CartQueue {
+    fn extend<T: IntoIterator<Item = BoxType>>(&mut self, items: T) {
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+impl CartQueue {
+    pub fn push(&mut self, item: BoxType) {
+        self.items.push(item);
+        self.ids.push(self.next_id);
+        self.next_id += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.ids.clear();
+    }
+
+    /// The ids of the queued items, in queue order
+    pub fn ids(&self) -> &[u64] {
+        &self.ids
+    }
+
+    /// The position in the queue of the item with the given id, if it is still queued
+    pub fn position(&self, id: u64) -> Option<usize> {
+        self.ids.iter().position(|queued| *queued == id)
+    }
+
+    /// Takes up to `count` items off the front of the queue
+    pub fn take_front(&mut self, count: usize) -> Vec<BoxType> {
+        let count = count.min(self.items.len());
+        self.ids.drain(0..count);
+        self.items.drain(0..count).collect()
+    }
+
+    pub fn remove(&mut self, idx: usize) -> BoxType {
+        self.ids.remove(idx);
+        self.items.remove(idx)
+    }
+
+    /// Moves the item at `from` so that it sits at position `to` in the queue
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        let id = self.ids.remove(from);
+        let item = self.items.remove(from);
+        self.ids.insert(to, id);
+        self.items.insert(to, item);
+    }
+}
+
 pub struct CartSpawningState {
-    pub items: Vec<BoxType>,
+    pub items: CartQueue,
+
+    /// The number of carts in the world, kept up to date by `count_active_carts`
     pub active_carts: usize,
+
+    /// How long carts wait in a loading bay before leaving, in seconds
+    pub patience_seconds: f32,
+
+    /// The time (since startup) that the last cart was spawned
+    pub last_spawn: f32,
     max_carts: usize,
 }
 
 impl Default for CartSpawningState {
     fn default() -> Self {
         Self {
-            items: vec![],
+            items: CartQueue::default(),
             active_carts: 0,
+            patience_seconds: 20.0,
+            last_spawn: 0.0,
             max_carts: 8,
         }
     }
 }
 
+impl CartSpawningState {
+    /// Estimates how many seconds until each queued item arrives in a loading bay. Carts are
+    /// sent every `CART_SPAWN_DELAY` seconds, and once the free loading bays are used up each
+    /// further cart has to wait for a parked cart to run out of patience.
+    pub fn estimated_arrivals(&self, cart_types: &CartTypes, now: f32) -> Vec<f32> {
+        let mut arrivals = Vec::with_capacity(self.items.len());
+        let free_bays = LOADING_BAYS_X.len().saturating_sub(self.active_carts);
+        let first_spawn = (self.last_spawn + CART_SPAWN_DELAY - now).max(0.0);
+
+        let mut remaining = self.items.len();
+        let mut batch = 0;
+
+        while remaining > 0 {
+            let definition = match cart_types.best_fit(remaining) {
+                Some(cart_type) => cart_types.get(cart_type),
+                None => break,
+            };
+
+            let mut wait = first_spawn + batch as f32 * CART_SPAWN_DELAY;
+            if batch >= free_bays {
+                wait +=
+                    self.patience_seconds * ((batch - free_bays) / LOADING_BAYS_X.len() + 1) as f32;
+            }
+
+            let bay_x = LOADING_BAYS_X[batch % LOADING_BAYS_X.len()];
+            let travel = (CART_SPAWN_X - bay_x) / definition.speed;
+
+            let count = definition.capacity().min(remaining);
+            arrivals.resize(arrivals.len() + count, wait + travel);
+
+            remaining -= count;
+            batch += 1;
+        }
+
+        arrivals
+    }
+}
+
 /// Not a system - this is a helper function used to spawn carts
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_cart(
     commands: &mut Commands,
     textures: &TextureAssets,
     animations: &AnimationAssets,
     location: Vec3,
-    box_types: [BoxType; 2],
+    bay: usize,
+    patience_seconds: f32,
+    cart_type: CartType,
+    definition: &CartTypeDefinition,
+    box_types: Vec<BoxType>,
 ) {
     commands
         .spawn_bundle(SpriteSheetBundle {
-            texture_atlas: textures.horse_and_cart.clone(),
+            texture_atlas: cart_type.get_texture_atlas(textures),
             transform: Transform::from_translation(location),
             ..Default::default()
         })
         .insert(AnimateWithSpeed {
-            speed: 30.,
-            target: vec![Vec3::new(-0.75 * WIDTH, location.y, CART_Z_POS)],
+            speed: definition.speed,
+            target: vec![Vec3::new(LOADING_BAYS_X[bay], location.y, CART_Z_POS)],
+        })
+        .insert(CartPatience {
+            bay,
+            timer: Timer::from_seconds(patience_seconds, false),
         })
         .insert(Cart {
-            front: Some(box_types[0]),
-            back: Some(box_types[1]),
+            cart_type,
+            slots: box_types.iter().map(|bt| Some(*bt)).collect(),
         })
         .insert(animations.cart.clone())
         .insert(AnimationState::default())
         .insert(WorldEntity)
         .with_children(|parent| {
-            parent
-                .spawn_bundle(SpriteSheetBundle {
-                    texture_atlas: textures.cart_boxes.clone(),
-                    sprite: TextureAtlasSprite {
-                        index: box_types[0] as usize * 2,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(CartCrate {
-                    is_front_slot: true,
-                });
-            parent
-                .spawn_bundle(SpriteSheetBundle {
-                    texture_atlas: textures.cart_boxes.clone(),
-                    sprite: TextureAtlasSprite {
-                        index: box_types[1] as usize * 2 + 1,
+            let pick_half_extents = definition.slot_pick_half_extents();
+
+            for (slot, (box_type, offset)) in
+                box_types.iter().zip(definition.slots.iter()).enumerate()
+            {
+                parent
+                    .spawn_bundle(SpriteSheetBundle {
+                        texture_atlas: textures.crates.clone(),
+                        sprite: TextureAtlasSprite {
+                            index: *box_type as usize,
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(offset.extend(0.01 * slot as f32))
+                            .with_scale(Vec3::splat(CART_CRATE_SCALE)),
                         ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(CartCrate {
-                    is_front_slot: false,
-                });
+                    })
+                    .insert(CartCrate { slot })
+                    .insert(Pickable)
+                    // be a bit flexible with the clicking (i.e. don't require directly on the crate)
+                    .insert(PickArea(pick_half_extents / CART_CRATE_SCALE));
+            }
 
             parent.spawn_bundle(SpriteBundle {
                 texture: textures.cart_shadow.clone(),
                 transform: Transform::from_xyz(0.0, -GRID_SIZE, -0.1),
                 ..default()
             });
+
+            parent
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::GREEN,
+                        custom_size: Some(Vec2::new(PATIENCE_METER_WIDTH, PATIENCE_METER_HEIGHT)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(GRID_SIZE, 1.25 * GRID_SIZE, 0.1),
+                    ..default()
+                })
+                .insert(CartPatienceMeter);
         });
 }
 
 #[derive(Default)]
 pub struct NextSpawnTime(pub u64);
 
+#[allow(clippy::too_many_arguments)]
 pub fn cart_spawning_system(
     mut commands: Commands,
     // tutorial_level: Res<CurrentTutorialLevel>, // TODO
-    time: Res<Time>,
+    time: Res<GameClock>,
+    time_of_day: Res<TimeOfDay>,
+    night_shift: Res<NightShift>,
     textures: Res<TextureAssets>,
     animations: Res<AnimationAssets>,
+    cart_types: Res<CartTypes>,
     mut spawning_state: ResMut<CartSpawningState>,
     mut cart_spawn_events: EventWriter<OnCartSpawned>,
-    mut last_spawn: Local<f32>,
+    carts: Query<&CartPatience>,
 ) {
-    // only spawn if we have capacity + both items are filled
-    if spawning_state.active_carts >= spawning_state.max_carts || spawning_state.items.len() < 2 {
+    // only spawn if we have capacity + there are items to deliver
+    if spawning_state.active_carts >= spawning_state.max_carts || spawning_state.items.is_empty() {
+        return;
+    }
+
+    // carts only run after dark if the company pays for a night shift
+    if time_of_day.is_night() && !night_shift.carts {
         return;
     }
 
     let elapsed = time.time_since_startup().as_secs_f32();
 
     // only spawn if its been 8 seconds since the last spawn
-    if elapsed - *last_spawn < CART_SPAWN_DELAY {
+    if elapsed - spawning_state.last_spawn < CART_SPAWN_DELAY {
         return;
     }
 
-    let cart_items = spawning_state.items.drain(0..2).collect::<Vec<_>>();
+    // carts drive in from the right, so fill the leftmost loading bay first
+    let occupied_bays = carts.iter().map(|cp| cp.bay).collect::<Vec<_>>();
+    let bay = match (0..LOADING_BAYS_X.len()).find(|bay| !occupied_bays.contains(bay)) {
+        Some(bay) => bay,
+        None => return,
+    };
+
+    // send the biggest cart that can be filled from the queue
+    let cart_type = match cart_types.best_fit(spawning_state.items.len()) {
+        Some(cart_type) => cart_type,
+        None => return,
+    };
+    let definition = cart_types.get(cart_type);
+
+    let cart_items = spawning_state.items.take_front(definition.capacity());
 
     // spawn a cart
     spawn_cart(
         &mut commands,
         &textures,
         &animations,
-        Vec3::new(WIDTH / 2.0 + GRID_SIZE * 5.0, -GRID_SIZE * 1.5, CART_Z_POS),
-        [cart_items[0], cart_items[1]], // TODO: spawn these based on the tutorial
+        Vec3::new(CART_SPAWN_X, -GRID_SIZE * 1.5, CART_Z_POS),
+        bay,
+        spawning_state.patience_seconds,
+        cart_type,
+        definition,
+        cart_items, // TODO: spawn these based on the tutorial
     );
     cart_spawn_events.send(OnCartSpawned);
 
-    *last_spawn = elapsed;
+    spawning_state.last_spawn = elapsed;
     spawning_state.active_carts += 1;
 }
+
+/// Keeps the number of active carts in sync with the carts in the world, however they were despawned
+pub fn count_active_carts(
+    mut spawning_state: ResMut<CartSpawningState>,
+    carts: Query<(), With<Cart>>,
+) {
+    let active_carts = carts.iter().count();
+    if spawning_state.active_carts != active_carts {
+        spawning_state.active_carts = active_carts;
+    }
+}
+
+/// Carts parked in a loading bay slowly lose patience, and leave once it runs out or once all
+/// their crates have been collected. Carts don't lose patience during the tutorial.
+#[allow(clippy::type_complexity)]
+pub fn update_cart_patience(
+    mut commands: Commands,
+    time: Res<GameClock>,
+    tutorial_level: Res<CurrentTutorialLevel>,
+    cart_types: Res<CartTypes>,
+    mut cart_left_events: EventWriter<OnCartLeft>,
+    mut carts: Query<
+        (Entity, &Transform, &Cart, &mut CartPatience, &Children),
+        (Without<AnimateWithSpeed>, Without<LeavingCart>),
+    >,
+    mut meters: Query<&mut Sprite, With<CartPatienceMeter>>,
+) {
+    for (cart_entity, cart_tx, cart, mut patience, children) in carts.iter_mut() {
+        if tutorial_level.0 >= TUTORIAL_COMPLETE_LEVEL {
+            patience.timer.tick(time.delta());
+        }
+
+        let remaining = 1.0 - patience.timer.percent();
+        for child in children.iter() {
+            if let Ok(mut meter) = meters.get_mut(*child) {
+                meter.color = Color::rgb(1.0 - remaining, remaining, 0.0);
+                meter.custom_size = Some(Vec2::new(
+                    remaining * PATIENCE_METER_WIDTH,
+                    PATIENCE_METER_HEIGHT,
+                ));
+            }
+        }
+
+        let unclaimed_crates = cart.slots.iter().filter(|slot| slot.is_some()).count();
+
+        if unclaimed_crates > 0 && !patience.timer.finished() {
+            continue;
+        }
+
+        info!(
+            "Cart {:?} leaving with {} unclaimed crates",
+            cart_entity, unclaimed_crates
+        );
+
+        commands
+            .entity(cart_entity)
+            .insert(LeavingCart)
+            .insert(AnimateWithSpeed {
+                speed: cart_types.get(cart.cart_type).speed,
+                target: vec![Vec3::new(-0.75 * WIDTH, cart_tx.translation.y, CART_Z_POS)],
+            });
+
+        // crates can't be taken from a cart once it has left the bay
+        for child in children.iter() {
+            commands.entity(*child).remove::<Pickable>();
+        }
+
+        cart_left_events.send(OnCartLeft { unclaimed_crates });
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{loader::TextureAssets, GRID_SIZE};
+
+/// The different kinds of cart that can deliver goods to the dock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CartType {
+    HandBarrow,
+    Cart,
+    Wagon,
+}
+
+impl CartType {
+    /// The sprite sheet the cart is animated from, which shares its layout with the horse and cart
+    pub fn get_texture_atlas(&self, textures: &TextureAssets) -> Handle<TextureAtlas> {
+        match self {
+            CartType::HandBarrow => textures.hand_barrow.clone(),
+            CartType::Cart => textures.horse_and_cart.clone(),
+            CartType::Wagon => textures.wagon.clone(),
+        }
+    }
+}
+
+/// Describes how a type of cart moves and carries crates
+#[derive(Debug, Clone)]
+pub struct CartTypeDefinition {
+    /// The offset of each cargo slot from the centre of the (unscaled) cart sprite
+    pub slots: Vec<Vec2>,
+    pub speed: f32,
+}
+
+impl CartTypeDefinition {
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// The half extents of the clickable area around each slot, relative to the (unscaled) cart
+    /// sprite. Clicks are a little more forgiving than the crate itself, but the area is shrunk
+    /// where slots are stacked closely so that one slot's area never covers its neighbour.
+    pub fn slot_pick_half_extents(&self) -> Vec2 {
+        let mut half_extents = Vec2::splat(0.5 * GRID_SIZE);
+
+        for (idx, a) in self.slots.iter().enumerate() {
+            for b in self.slots.iter().skip(idx + 1) {
+                let gap = (*a - *b).abs();
+                let overlaps = gap.x < 2.0 * half_extents.x && gap.y < 2.0 * half_extents.y;
+
+                if !overlaps {
+                    continue;
+                }
+
+                // separate the slots along the axis they are furthest apart on
+                if gap.x >= gap.y {
+                    half_extents.x = half_extents.x.min(0.5 * gap.x);
+                } else {
+                    half_extents.y = half_extents.y.min(0.5 * gap.y);
+                }
+            }
+        }
+
+        half_extents
+    }
+}
+
+/// A resource containing the definition of every cart type
+pub struct CartTypes(pub HashMap<CartType, CartTypeDefinition>);
+
+impl CartTypes {
+    pub fn get(&self, cart_type: CartType) -> &CartTypeDefinition {
+        &self.0[&cart_type]
+    }
+
+    /// Returns the largest type of cart which can be filled by the given number of crates
+    pub fn best_fit(&self, num_crates: usize) -> Option<CartType> {
+        self.0
+            .iter()
+            .filter(|(_, def)| def.capacity() <= num_crates)
+            .max_by_key(|(_, def)| def.capacity())
+            .map(|(cart_type, _)| *cart_type)
+    }
+}
+
+impl Default for CartTypes {
+    fn default() -> Self {
+        let mut hm = HashMap::new();
+
+        // the cart bed is on the right hand side of the horse and cart sprite
+        hm.insert(
+            CartType::HandBarrow,
+            CartTypeDefinition {
+                slots: vec![Vec2::new(48.0, 2.0)],
+                speed: 40.0,
+            },
+        );
+        hm.insert(
+            CartType::Cart,
+            CartTypeDefinition {
+                slots: vec![Vec2::new(32.0, 2.0), Vec2::new(64.0, 2.0)],
+                speed: 30.0,
+            },
+        );
+        hm.insert(
+            CartType::Wagon,
+            CartTypeDefinition {
+                slots: vec![
+                    Vec2::new(32.0, 2.0),
+                    Vec2::new(64.0, 2.0),
+                    Vec2::new(32.0, 2.0 + 0.75 * GRID_SIZE),
+                    Vec2::new(64.0, 2.0 + 0.75 * GRID_SIZE),
+                ],
+                speed: 22.0,
+            },
+        );
+
+        Self(hm)
+    }
+}
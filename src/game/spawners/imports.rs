@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        clock::GameClock,
+        components::{BoxType, InboundCargo, Sinking, WorldEntity},
+        spawners::{
+            lanes::{SailingDirection, ShipLane},
+            spawn_physics_crate,
+        },
+        WAREHOUSE_LOCATION,
+    },
+    loader::TextureAssets,
+    GRID_SIZE,
+};
+
+/// The height above the warehouse floor that imported crates are dropped from
+const IMPORT_DROP_HEIGHT: f32 = 3.0 * GRID_SIZE;
+
+/// Event triggered when a ship unloads imported goods onto the warehouse
+pub struct OnImportsUnloaded {
+    pub crates: Vec<BoxType>,
+}
+
+/// Unloads a ship's imports onto the warehouse as physics crates once it sails past
+#[allow(clippy::type_complexity)]
+pub fn unload_inbound_cargo(
+    mut commands: Commands,
+    time: Res<GameClock>,
+    textures: Res<TextureAssets>,
+    mut unload_events: EventWriter<OnImportsUnloaded>,
+    ships: Query<(Entity, &Transform, &ShipLane, &InboundCargo), Without<Sinking>>,
+) {
+    for (ship_entity, ship_tx, ship_lane, cargo) in ships.iter() {
+        let has_passed_warehouse = match ship_lane.direction {
+            SailingDirection::LeftToRight => ship_tx.translation.x >= WAREHOUSE_LOCATION.x,
+            SailingDirection::RightToLeft => ship_tx.translation.x <= WAREHOUSE_LOCATION.x,
+        };
+
+        if !has_passed_warehouse {
+            continue;
+        }
+
+        commands.entity(ship_entity).remove::<InboundCargo>();
+
+        if cargo.0.is_empty() {
+            continue;
+        }
+
+        info!("Ship {:?} unloading imports {:?}", ship_entity, cargo.0);
+
+        for (idx, box_type) in cargo.0.iter().enumerate() {
+            let offset = (idx as f32 - 0.5 * (cargo.0.len() - 1) as f32) * 1.5 * GRID_SIZE;
+
+            let sprite = commands
+                .spawn_bundle(SpriteBundle {
+                    texture: box_type.get_image(&textures),
+                    transform: Transform::from_xyz(
+                        WAREHOUSE_LOCATION.x + offset,
+                        WAREHOUSE_LOCATION.y + IMPORT_DROP_HEIGHT,
+                        5.0,
+                    ),
+                    ..default()
+                })
+                .insert(WorldEntity)
+                .id();
+
+            spawn_physics_crate(
+                &mut commands,
+                sprite,
+                *box_type,
+                time.seconds_since_startup(),
+                Vec2::ZERO,
+            );
+        }
+
+        unload_events.send(OnImportsUnloaded {
+            crates: cargo.0.clone(),
+        });
+    }
+}
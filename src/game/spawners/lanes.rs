@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{GRID_SIZE, WIDTH};
+
+/// How far off screen (as a proportion of the screen width) ships spawn and despawn
+pub const SHIP_OFFSCREEN_X: f32 = 0.7 * WIDTH;
+
+/// A single lane that ships can sail along. Lanes further from the dock should be
+/// higher up the screen, further back (lower z), smaller and slower.
+#[derive(Clone, Debug)]
+pub struct ShippingLane {
+    pub y: f32,
+    pub z: f32,
+    pub speed: f32,
+    pub scale: f32,
+}
+
+/// The shipping lanes available in the harbour, ordered from nearest to furthest
+pub struct ShippingLanes(pub Vec<ShippingLane>);
+
+impl Default for ShippingLanes {
+    fn default() -> Self {
+        Self(vec![
+            ShippingLane {
+                y: -10.0 * GRID_SIZE,
+                z: 8.0,
+                speed: 50.0,
+                scale: 1.0,
+            },
+            ShippingLane {
+                y: -8.5 * GRID_SIZE,
+                z: 6.5,
+                speed: 38.0,
+                scale: 0.8,
+            },
+            ShippingLane {
+                y: -7.0 * GRID_SIZE,
+                z: 5.0,
+                speed: 28.0,
+                scale: 0.6,
+            },
+        ])
+    }
+}
+
+impl ShippingLanes {
+    /// Picks a random lane which isn't in the list of occupied lanes, if one is available
+    pub fn choose_free_lane(&self, rng: &mut ThreadRng, occupied: &[usize]) -> Option<usize> {
+        let free = (0..self.0.len())
+            .filter(|idx| !occupied.contains(idx))
+            .collect::<Vec<_>>();
+
+        free.choose(rng).copied()
+    }
+}
+
+/// The direction a ship is sailing across the screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SailingDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl SailingDirection {
+    pub fn random(rng: &mut ThreadRng) -> Self {
+        if rng.gen_bool(0.5) {
+            SailingDirection::LeftToRight
+        } else {
+            SailingDirection::RightToLeft
+        }
+    }
+
+    /// The sign of the x velocity of a ship sailing in this direction
+    pub fn sign(&self) -> f32 {
+        match self {
+            SailingDirection::LeftToRight => 1.0,
+            SailingDirection::RightToLeft => -1.0,
+        }
+    }
+}
+
+/// Marks a ship (wave) entity as occupying a shipping lane
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ShipLane {
+    pub lane: usize,
+    pub direction: SailingDirection,
+}
+
+impl ShipLane {
+    /// The location a ship in this lane spawns at, off screen
+    pub fn start(&self, lane: &ShippingLane) -> Vec3 {
+        Vec3::new(-self.direction.sign() * SHIP_OFFSCREEN_X, lane.y, lane.z)
+    }
+
+    /// The location a ship in this lane sails to before despawning, off screen
+    pub fn end(&self, lane: &ShippingLane) -> Vec3 {
+        Vec3::new(self.direction.sign() * SHIP_OFFSCREEN_X, lane.y, lane.z)
+    }
+}
@@ -1,25 +1,36 @@
 use bevy::prelude::*;
 use heron::PhysicsLayer;
-use iyes_loopless::prelude::IntoConditionalSystem;
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionHelpers, IntoConditionalSystem};
 
-use crate::GameState;
+use crate::{game::OnRainStart, GameState};
 
 mod cart;
+pub mod cart_types;
+mod imports;
+pub mod lanes;
 pub mod request;
+pub mod schedule;
 mod ship;
+pub mod suppliers;
 mod torch;
 pub use torch::spawn_torch;
 mod physics_crate;
-pub use physics_crate::spawn_physics_crate;
+pub use physics_crate::{spawn_physics_crate, CRATE_FRICTION};
 
-pub use self::cart::{CartSpawningState, OnCartSpawned};
-pub use self::ship::OnShipSpawned;
+pub use self::cart::{CartSpawningState, OnCartLeft, OnCartSpawned};
+pub use self::cart_types::CartTypes;
+pub use self::imports::OnImportsUnloaded;
+pub use self::lanes::ShippingLanes;
+pub use self::schedule::ShipSchedule;
+pub use self::ship::{spawn_ship_at, OnShipFoundered, OnShipSpawned};
+pub use self::suppliers::{OnSupplierDelivery, SupplierOrders};
 
 // Define your physics layers
 #[derive(PhysicsLayer)]
 pub enum GamePhysicsLayer {
     Ship,
     Crate,
+    Conveyor,
     // Warehouse,
     // Factory,
 }
@@ -29,10 +40,29 @@ pub struct SpawningPlugin;
 impl Plugin for SpawningPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(CartSpawningState::default())
+            .insert_resource(CartTypes::default())
+            .insert_resource(ShippingLanes::default())
+            .insert_resource(ShipSchedule::default())
+            .insert_resource(SupplierOrders::default())
             .add_event::<OnCartSpawned>()
+            .add_event::<OnCartLeft>()
             .add_event::<OnShipSpawned>()
+            .add_event::<OnShipFoundered>()
+            .add_event::<OnImportsUnloaded>()
+            .add_event::<OnSupplierDelivery>()
+            .add_enter_system(GameState::Playing, schedule::reset_ship_schedule)
+            .add_enter_system(GameState::Playing, suppliers::reset_supplier_orders)
+            .add_system(cart::count_active_carts.run_in_state(GameState::Playing))
             .add_system(cart::cart_spawning_system.run_in_state(GameState::Playing))
+            .add_system(cart::update_cart_patience.run_in_state(GameState::Playing))
             .add_system(ship::ship_queuing_system.run_in_state(GameState::Playing))
-            .add_system(ship::ship_spawn_on_timer_expiry.run_in_state(GameState::Playing));
+            .add_system(ship::ship_spawn_on_timer_expiry.run_in_state(GameState::Playing))
+            .add_system(imports::unload_inbound_cargo.run_in_state(GameState::Playing))
+            .add_system(suppliers::deliver_supplier_orders.run_in_state(GameState::Playing))
+            .add_system(
+                ship::founder_overloaded_ships
+                    .run_in_state(GameState::Playing)
+                    .run_on_event::<OnRainStart>(),
+            );
     }
 }
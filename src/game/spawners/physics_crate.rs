@@ -1,22 +1,33 @@
 use crate::{
-    game::components::{BoxType, FollowMouse, PhysicsCrate, WorldEntity},
+    game::components::{
+        BoxType, FollowMouse, PhysicsCrate, Pickable, PreviousVelocity, WorldEntity,
+    },
     GRID_SIZE,
 };
 use bevy::prelude::*;
-use heron::{CollisionLayers, CollisionShape, Collisions, PhysicMaterial, RigidBody, Velocity};
+use heron::{
+    Acceleration, CollisionLayers, CollisionShape, Collisions, PhysicMaterial, RigidBody, Velocity,
+};
 
 use super::GamePhysicsLayer;
 
+/// How much crates grip the surfaces they rest on, in fine weather
+pub const CRATE_FRICTION: f32 = 0.25;
+
 pub fn spawn_physics_crate(
     commands: &mut Commands,
     sprite_entity: Entity,
     box_type: BoxType,
+    spawned_at: f64,
     initial_velocity: Vec2,
 ) {
     commands
         .entity(sprite_entity)
         .remove::<FollowMouse>()
-        .insert(PhysicsCrate { box_type })
+        .insert(PhysicsCrate {
+            box_type,
+            spawned_at,
+        })
         .insert(RigidBody::Dynamic)
         .insert(CollisionShape::Cuboid {
             half_extends: Vec3::new(GRID_SIZE / 2.75, GRID_SIZE / 2.75, GRID_SIZE / 2.75),
@@ -25,14 +36,21 @@ pub fn spawn_physics_crate(
         .insert(
             CollisionLayers::none()
                 .with_group(GamePhysicsLayer::Crate)
-                .with_masks(&[GamePhysicsLayer::Ship, GamePhysicsLayer::Crate]),
+                .with_masks(&[
+                    GamePhysicsLayer::Ship,
+                    GamePhysicsLayer::Crate,
+                    GamePhysicsLayer::Conveyor,
+                ]),
         )
         .insert(PhysicMaterial {
-            friction: 0.25,
+            friction: CRATE_FRICTION,
             density: 1.0,
             ..Default::default()
         })
         .insert(Velocity::from_linear(initial_velocity.extend(0.0)))
+        .insert(PreviousVelocity(initial_velocity.extend(0.0)))
+        .insert(Acceleration::default())
         .insert(Collisions::default())
+        .insert(Pickable)
         .insert(WorldEntity);
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
 use crate::{
@@ -5,12 +7,19 @@ use crate::{
     loader::TextureAssets,
 };
 
+/// Not a system - spawns a ship request in the ship bar. The countdown can be started part way
+/// through, for requests restored from a saved game.
 pub fn spawn_ship_request_icon(
     layout: &mut ChildBuilder,
     textures: &TextureAssets,
     demands: Vec<BoxType>,
+    imports: Vec<BoxType>,
     expiry: f32,
+    countdown_elapsed: Duration,
 ) {
+    let mut countdown = Timer::from_seconds(15.0, false);
+    countdown.set_elapsed(countdown_elapsed);
+
     layout
         .spawn_bundle(NodeBundle {
             style: Style {
@@ -37,9 +46,10 @@ pub fn spawn_ship_request_icon(
                 })
                 .insert(SpawnShipRequest {
                     demands: demands.clone(),
+                    imports: imports.clone(),
                     expiry,
                 })
-                .insert(CountDownTimer(Timer::from_seconds(15.0, false)));
+                .insert(CountDownTimer(countdown));
 
             parent.spawn_bundle(ImageBundle {
                 image: textures.ship_small.clone().into(),
@@ -52,5 +62,19 @@ pub fn spawn_ship_request_icon(
                     ..default()
                 });
             }
+
+            if !imports.is_empty() {
+                parent.spawn_bundle(ImageBundle {
+                    image: textures.down.clone().into(),
+                    ..default()
+                });
+
+                for import in imports.iter() {
+                    parent.spawn_bundle(ImageBundle {
+                        image: import.get_image(textures).into(),
+                        ..default()
+                    });
+                }
+            }
         });
 }
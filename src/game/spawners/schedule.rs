@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    components::BoxType,
+    demand::{DemandContext, DemandGenerator},
+    factory::recipes::Recipes,
+    game_mode::GameMode,
+    rng::RandomSpawnTimer,
+};
+
+/// The number of ships to forecast ahead of time
+pub const SCHEDULE_LENGTH: usize = 5;
+
+/// How long before a ship arrives that its request appears in the ship bar
+pub const SHIP_REQUEST_WARNING_SECONDS: f64 = 15.0;
+
+/// The chance a scheduled ship brings imported goods with it
+pub const IMPORT_CHANCE: f64 = 0.3;
+
+/// The most crates a ship can bring in as imports
+pub const MAX_IMPORTS: usize = 2;
+
+/// Goods that ships can bring in from overseas
+pub const IMPORTABLE_GOODS: [BoxType; 3] = [BoxType::Glassware, BoxType::Grapes, BoxType::Honey];
+
+/// A ship that is expected to arrive at some point in the future
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledShip {
+    /// The time the ship will arrive, in seconds since startup
+    pub arrival: f64,
+    pub demands: Vec<BoxType>,
+
+    /// Goods the ship will unload at the warehouse as it passes
+    pub imports: Vec<BoxType>,
+}
+
+/// A resource holding the forecast of upcoming ship arrivals, ordered by arrival time
+pub struct ShipSchedule {
+    pub ships: Vec<ScheduledShip>,
+    timer: RandomSpawnTimer,
+    generator: Box<dyn DemandGenerator>,
+    started_at: Option<f64>,
+}
+
+impl Default for ShipSchedule {
+    fn default() -> Self {
+        Self::new(GameMode::default())
+    }
+}
+
+/// The parts of the ship schedule kept in a saved game. The demand generator is recreated
+/// from the game mode when the game is restored.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedShipSchedule {
+    pub ships: Vec<ScheduledShip>,
+    pub timer: RandomSpawnTimer,
+    pub started_at: Option<f64>,
+}
+
+impl ShipSchedule {
+    pub fn new(game_mode: GameMode) -> Self {
+        Self {
+            ships: vec![],
+            timer: RandomSpawnTimer::default(),
+            generator: game_mode.demand_generator(),
+            started_at: None,
+        }
+    }
+
+    pub fn to_saved(&self) -> SavedShipSchedule {
+        SavedShipSchedule {
+            ships: self.ships.clone(),
+            timer: self.timer.clone(),
+            started_at: self.started_at,
+        }
+    }
+
+    pub fn from_saved(game_mode: GameMode, saved: SavedShipSchedule) -> Self {
+        Self {
+            ships: saved.ships,
+            timer: saved.timer,
+            generator: game_mode.demand_generator(),
+            started_at: saved.started_at,
+        }
+    }
+
+    /// Tops up the schedule so that it always forecasts `SCHEDULE_LENGTH` ships. The first
+    /// time this is called the first ship is scheduled to arrive after the warning period.
+    /// Each new ship has the given chance of swapping one of its demands for an extra demand.
+    /// Fewer ships sail at night, so arrivals that would fall at night are spaced further apart.
+    pub fn fill(
+        &mut self,
+        rng: &mut ThreadRng,
+        recipes: &Recipes,
+        current_time: f64,
+        extra_demands: &[(BoxType, f64)],
+        is_night_at: impl Fn(f64) -> bool,
+    ) {
+        let started_at = match self.started_at {
+            Some(t) => t,
+            None => {
+                self.timer
+                    .restart_at(current_time + SHIP_REQUEST_WARNING_SECONDS);
+                self.started_at = Some(current_time);
+                current_time
+            }
+        };
+
+        while self.ships.len() < SCHEDULE_LENGTH {
+            let mut arrival = self.timer.advance(rng);
+            if is_night_at(arrival) {
+                arrival = self.timer.advance(rng);
+            }
+
+            let mut demands = self.generator.generate(
+                rng,
+                &DemandContext {
+                    elapsed: arrival - started_at,
+                    recipes,
+                },
+            );
+
+            for (good, chance) in extra_demands {
+                if !demands.is_empty() && !demands.contains(good) && rng.gen_bool(*chance) {
+                    let idx = rng.gen_range(0..demands.len());
+                    demands[idx] = *good;
+                }
+            }
+
+            let imports = if rng.gen_bool(IMPORT_CHANCE) {
+                (0..rng.gen_range(1..=MAX_IMPORTS))
+                    .map(|_| *IMPORTABLE_GOODS.choose(rng).unwrap())
+                    .collect()
+            } else {
+                vec![]
+            };
+
+            info!(
+                "Scheduled a ship to arrive at {:.1}s, next spawn range is {:?}s",
+                arrival, self.timer.spawn_range
+            );
+            self.ships.push(ScheduledShip {
+                arrival,
+                demands,
+                imports,
+            });
+        }
+    }
+
+    /// Does the schedule need topping up with more ships
+    pub fn needs_filling(&self) -> bool {
+        self.ships.len() < SCHEDULE_LENGTH
+    }
+
+    /// True if the request for the next scheduled ship should be shown at the given time
+    pub fn is_next_due(&self, current_time: f64) -> bool {
+        match self.ships.first() {
+            Some(next) => next.arrival - SHIP_REQUEST_WARNING_SECONDS <= current_time,
+            None => false,
+        }
+    }
+}
+
+pub fn reset_ship_schedule(mut schedule: ResMut<ShipSchedule>, game_mode: Res<GameMode>) {
+    *schedule = ShipSchedule::new(*game_mode);
+}
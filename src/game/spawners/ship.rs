@@ -1,48 +1,69 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use heron::{CollisionLayers, CollisionShape, RigidBody};
-use rand::{seq::SliceRandom, thread_rng, Rng};
+use rand::{thread_rng, Rng};
 
 use crate::{
     game::{
+        calendar::Calendar,
+        clock::GameClock,
         components::{
-            AnimateWithSpeed, BoxType, CountDownTimer, Ship, ShipDemandItemMarker, ShipHold,
-            SpawnShipRequest, TopUiBar, Wave, WorldEntity, BOX_DEMANDS,
+            AnimateWithSpeed, BoxType, CountDownTimer, InboundCargo, Ship, ShipDemandItemMarker,
+            ShipHold, Sinking, SpawnShipRequest, TopUiBar, Wave, WorldEntity,
+        },
+        day_night_cycle::TimeOfDay,
+        factory::recipes::Recipes,
+        spawners::{
+            request::spawn_ship_request_icon,
+            schedule::{ShipSchedule, SHIP_REQUEST_WARNING_SECONDS},
         },
-        rng::RandomSpawnTimer,
-        spawners::request::spawn_ship_request_icon,
-        ui::tutorial::CurrentTutorialLevel,
+        ui::tutorial::{CurrentTutorialLevel, TUTORIAL_COMPLETE_LEVEL},
         AnimationState,
     },
     loader::{AnimationAssets, TextureAssets},
-    GRID_SIZE, WIDTH,
+    GRID_SIZE,
 };
 
-use super::GamePhysicsLayer;
-
-pub const SHIP_SAILING_POSITION_Y: f32 = -10.0 * GRID_SIZE;
-pub const SHIP_SPAWN_OFFSCREEN_POSITION: Vec3 =
-    Vec3::new(-0.7 * WIDTH, SHIP_SAILING_POSITION_Y, 8.0);
+use super::{
+    lanes::{SailingDirection, ShipLane, ShippingLanes},
+    GamePhysicsLayer,
+};
 
 pub const MAX_SPAWN_REQUESTS: usize = 5;
 
-pub const SHIP_SPEED: f32 = 40.0;
+pub const SHIP_WIDTH: f32 = 288.0;
 
-pub const SPAWN_SPEED_UP_RATE_PER_SECOND: f64 = 0.05;
+/// The number of crates a ship can carry before it risks foundering in a storm
+pub const SAFE_SHIP_LOAD: usize = 3;
 
-pub const SHIP_WIDTH: f32 = 288.0;
+/// The chance an overloaded ship founders when a storm starts
+pub const CHANCE_OF_FOUNDERING: f64 = 0.4;
+
+/// How long it takes a foundering ship to disappear beneath the waves
+pub const SINKING_DURATION: f32 = 4.0;
 
 pub struct OnShipSpawned;
 
-/// Periodically queues up a RequestShip component and button in the ship bar
+/// Event triggered when an overloaded ship founders in a storm
+pub struct OnShipFoundered {
+    pub cargo: Vec<BoxType>,
+    pub location: Vec3,
+}
+
+/// Forecasts ship arrivals into the ship schedule, and queues up a RequestShip component and
+/// button in the ship bar shortly before each scheduled ship is due.
 /// When the timer gets to 0, the ship spawns and sails across the screen.
 #[allow(clippy::too_many_arguments)]
 pub fn ship_queuing_system(
     mut commands: Commands,
-    time: Res<Time>,
+    time: Res<GameClock>,
     textures: Res<TextureAssets>,
+    recipes: Res<Recipes>,
+    calendar: Res<Calendar>,
+    time_of_day: Res<TimeOfDay>,
     mut tutorial_level: ResMut<CurrentTutorialLevel>,
-    mut event_test: Local<RandomSpawnTimer>,
-    mut next_test: Local<f64>,
+    mut schedule: ResMut<ShipSchedule>,
     spawn_requests: Query<&SpawnShipRequest>,
     top_bar_query: Query<Entity, With<TopUiBar>>,
 ) {
@@ -56,7 +77,9 @@ pub fn ship_queuing_system(
                 layout,
                 &textures,
                 vec![BoxType::Cider],
+                vec![],
                 (time.seconds_since_startup() + 8.0) as f32,
+                Duration::ZERO,
             );
         });
 
@@ -64,64 +87,96 @@ pub fn ship_queuing_system(
         return;
     }
 
-    if tutorial_level.0 < 9 {
+    if tutorial_level.0 < TUTORIAL_COMPLETE_LEVEL {
         return;
     }
 
     let elapsed = time.seconds_since_startup();
-    if elapsed < *next_test {
-        return;
+    // only take the schedule mutably when it changes so the schedule UI isn't rebuilt every frame
+    if schedule.needs_filling() {
+        let mut rng = thread_rng();
+        schedule.fill(
+            &mut rng,
+            &recipes,
+            elapsed,
+            &calendar.extra_demands(),
+            |arrival| time_of_day.is_night_in(arrival - elapsed),
+        );
     }
 
-    if spawn_requests.iter().count() >= MAX_SPAWN_REQUESTS {
+    // if the ship bar is full or the port is closed, scheduled ships are delayed
+    if !schedule.is_next_due(elapsed)
+        || spawn_requests.iter().count() >= MAX_SPAWN_REQUESTS
+        || calendar.is_port_closed()
+    {
         return;
     }
 
-    *next_test = elapsed + 1.0;
+    let scheduled = schedule.ships.remove(0);
+    info!("Spawning a ship request for {:?}", scheduled.demands);
 
-    // speed up ship spawns,
-    let new_min = (event_test.spawn_range.start - SPAWN_SPEED_UP_RATE_PER_SECOND).max(8.0); // reduce by 0.05 / second (every 20 seconds reduce gap by 1s), up to 8s
-    let new_max =
-        (new_min + (event_test.spawn_range.end - event_test.spawn_range.start) - 0.01).max(9.0);
-    event_test.spawn_range = new_min..new_max;
+    // ships held back by a full ship bar still give the player the full warning
+    let expiry = scheduled
+        .arrival
+        .max(elapsed + SHIP_REQUEST_WARNING_SECONDS);
 
-    let mut rng = thread_rng();
-    if event_test.tick(&mut rng, elapsed) {
-        info!(
-            "Spawning a ship request, next spawn range is {:?}s",
-            event_test.spawn_range
-        );
-
-        let top_bar = top_bar_query.single();
+    let top_bar = top_bar_query.single();
 
-        let mut demands = vec![];
-        for _ in 0..rng.gen_range(1..=3) {
-            demands.push(*BOX_DEMANDS.choose(&mut rng).unwrap());
-        }
-
-        commands.entity(top_bar).with_children(|layout| {
-            spawn_ship_request_icon(
-                layout,
-                &textures,
-                demands,
-                (time.seconds_since_startup() + event_test.spawn_range.start) as f32,
-            );
-        });
-    }
+    commands.entity(top_bar).with_children(|layout| {
+        spawn_ship_request_icon(
+            layout,
+            &textures,
+            scheduled.demands,
+            scheduled.imports,
+            expiry as f32,
+            Duration::ZERO,
+        );
+    });
 }
 
-/// Launches ships when their timer runs out
+/// Launches ships when their timer runs out. If all the shipping lanes are busy
+/// the request waits until a lane becomes free, or until the port reopens.
+#[allow(clippy::too_many_arguments)]
 pub fn ship_spawn_on_timer_expiry(
     mut commands: Commands,
     textures: Res<TextureAssets>,
     animations: Res<AnimationAssets>,
+    lanes: Res<ShippingLanes>,
+    calendar: Res<Calendar>,
     mut spawn_events: EventWriter<OnShipSpawned>,
     requests: Query<(&Parent, &CountDownTimer, &SpawnShipRequest)>,
+    sailing_ships: Query<&ShipLane>,
 ) {
+    if calendar.is_port_closed() {
+        return;
+    }
+
+    let mut rng = thread_rng();
+    let mut occupied = sailing_ships.iter().map(|sl| sl.lane).collect::<Vec<_>>();
+
     for (parent_entity, timer, request) in requests.iter() {
         if timer.0.finished() {
-            info!("Launching a ship due to timer");
-            spawn_ship(&mut commands, &textures, &animations, request.clone());
+            let lane = match lanes.choose_free_lane(&mut rng, &occupied) {
+                Some(lane) => lane,
+                None => {
+                    // all the lanes are busy, try again next frame
+                    continue;
+                }
+            };
+
+            info!("Launching a ship in lane {} due to timer", lane);
+            occupied.push(lane);
+            spawn_ship(
+                &mut commands,
+                &textures,
+                &animations,
+                &lanes,
+                ShipLane {
+                    lane,
+                    direction: SailingDirection::random(&mut rng),
+                },
+                request.clone(),
+            );
 
             // despawn the spawn indicator
             commands.entity(parent_entity.get()).despawn_recursive();
@@ -130,25 +185,61 @@ pub fn ship_spawn_on_timer_expiry(
     }
 }
 
-/// Spawns a ship in the game world based on a RequestShip
+/// Spawns a ship in the game world based on a RequestShip, sailing along the given lane
 pub fn spawn_ship(
     commands: &mut Commands,
     textures: &TextureAssets,
     animations: &AnimationAssets,
+    lanes: &ShippingLanes,
+    ship_lane: ShipLane,
+    request: SpawnShipRequest,
+) -> Entity {
+    let location = ship_lane.start(&lanes.0[ship_lane.lane]);
+    spawn_ship_at(
+        commands,
+        textures,
+        animations,
+        lanes,
+        ship_lane,
+        request,
+        location,
+        vec![],
+    )
+}
+
+/// Spawns a ship part way along its lane with crates already in its hold, used to restore
+/// ships from a saved game
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_ship_at(
+    commands: &mut Commands,
+    textures: &TextureAssets,
+    animations: &AnimationAssets,
+    lanes: &ShippingLanes,
+    ship_lane: ShipLane,
     request: SpawnShipRequest,
+    location: Vec3,
+    crates: Vec<BoxType>,
 ) -> Entity {
     let mut rng = thread_rng();
     let mut entity: Option<Entity> = None;
 
+    let lane = &lanes.0[ship_lane.lane];
+    let flip_x = ship_lane.direction == SailingDirection::RightToLeft;
+
     commands
         .spawn_bundle(SpriteBundle {
             texture: textures.waves.clone(),
-            transform: Transform::from_translation(SHIP_SPAWN_OFFSCREEN_POSITION),
+            sprite: Sprite {
+                flip_x,
+                ..default()
+            },
+            transform: Transform::from_translation(location).with_scale(Vec3::splat(lane.scale)),
             ..default()
         })
         .insert_bundle((
+            // heron ignores the transform scale, so scale the collider by hand
             CollisionShape::Cuboid {
-                half_extends: Vec3::new(SHIP_WIDTH / 2.2, 18.0, 30.0),
+                half_extends: Vec3::new(lane.scale * SHIP_WIDTH / 2.2, lane.scale * 18.0, 30.0),
                 border_radius: None,
             },
             RigidBody::Sensor,
@@ -158,17 +249,15 @@ pub fn spawn_ship(
         ))
         .insert(WorldEntity)
         .insert(AnimateWithSpeed {
-            speed: SHIP_SPEED,
-            target: vec![Vec3::new(
-                -SHIP_SPAWN_OFFSCREEN_POSITION.x,
-                SHIP_SPAWN_OFFSCREEN_POSITION.y,
-                SHIP_SPAWN_OFFSCREEN_POSITION.z,
-            )],
+            speed: lane.speed,
+            target: vec![ship_lane.end(lane)],
         })
         .insert(Wave)
+        .insert(ship_lane)
+        .insert(InboundCargo(request.imports.clone()))
         .with_children(|child_commands| {
             let ship_hold = ShipHold {
-                crates: vec![],
+                crates,
                 demands: request.demands.clone(),
             };
 
@@ -176,7 +265,15 @@ pub fn spawn_ship(
                 child_commands
                     .spawn_bundle(SpriteSheetBundle {
                         texture_atlas: textures.ship.clone(),
-                        transform: Transform::from_xyz(0.5 * GRID_SIZE, 0.0, -0.5),
+                        sprite: TextureAtlasSprite {
+                            flip_x,
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(
+                            0.5 * GRID_SIZE * ship_lane.direction.sign(),
+                            0.0,
+                            -0.5,
+                        ),
                         ..Default::default()
                     })
                     .insert(Ship::new(&mut rng))
@@ -184,12 +281,15 @@ pub fn spawn_ship(
                     .insert(AnimationState::default())
                     .insert(ship_hold.clone())
                     .with_children(|ship_child_commands| {
-                        for (idx, demand) in ship_hold.demands.iter().enumerate() {
+                        for (idx, demand) in ship_hold.get_unmet_demands().iter().enumerate() {
                             ship_child_commands
                                 .spawn_bundle(SpriteBundle {
                                     texture: demand.get_image(textures),
+                                    // mirror the icons along with the hull so they stay
+                                    // in the same place on ships sailing either way
                                     transform: Transform::from_xyz(
-                                        -110. + (idx as f32 * GRID_SIZE),
+                                        ship_lane.direction.sign()
+                                            * (-110. + (idx as f32 * GRID_SIZE)),
                                         -120.,
                                         2.,
                                     ),
@@ -204,3 +304,45 @@ pub fn spawn_ship(
 
     entity.unwrap()
 }
+
+/// When a storm starts, overloaded ships have a chance to founder. Foundering ships stop sailing,
+/// stop accepting crates and sink beneath the waves, losing all their cargo.
+#[allow(clippy::type_complexity)]
+pub fn founder_overloaded_ships(
+    mut commands: Commands,
+    mut founder_events: EventWriter<OnShipFoundered>,
+    waves: Query<(Entity, &Children, &GlobalTransform), (With<Wave>, Without<Sinking>)>,
+    holds: Query<&ShipHold>,
+) {
+    let mut rng = thread_rng();
+
+    for (wave_entity, children, wave_tx) in waves.iter() {
+        for child in children.iter() {
+            if let Ok(hold) = holds.get(*child) {
+                if hold.crates.len() <= SAFE_SHIP_LOAD || !rng.gen_bool(CHANCE_OF_FOUNDERING) {
+                    break;
+                }
+
+                info!(
+                    "Ship {:?} foundered in the storm with {} crates aboard",
+                    wave_entity,
+                    hold.crates.len()
+                );
+
+                commands
+                    .entity(wave_entity)
+                    .remove::<AnimateWithSpeed>()
+                    .remove::<RigidBody>()
+                    .remove::<CollisionShape>()
+                    .insert(Sinking(Timer::from_seconds(SINKING_DURATION, false)));
+
+                founder_events.send(OnShipFoundered {
+                    cargo: hold.crates.clone(),
+                    location: wave_tx.translation(),
+                });
+
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    clock::GameClock, components::BoxType, day_night_cycle::TIME_OF_DAY_HOURS_PER_GAME_SECONDS,
+    spawners::CartSpawningState,
+};
+
+/// Event triggered when a standing order is added to the cart delivery queue, so it can be paid for
+pub struct OnSupplierDelivery {
+    pub good: BoxType,
+    pub quantity: usize,
+    pub cost: f32,
+}
+
+/// A standing order a supplier is willing to take on
+#[derive(Clone, Copy, Debug)]
+pub struct SupplierOffer {
+    pub good: BoxType,
+    pub quantity: usize,
+
+    /// How often the goods are delivered, in in-game hours
+    pub interval_hours: f32,
+
+    /// The cost of each delivery
+    pub cost: f32,
+}
+
+/// The standing orders players can place from the supplier panel
+pub const SUPPLIER_OFFERS: [SupplierOffer; 5] = [
+    SupplierOffer {
+        good: BoxType::Glassware,
+        quantity: 2,
+        interval_hours: 4.0,
+        cost: 6.0,
+    },
+    SupplierOffer {
+        good: BoxType::Apples,
+        quantity: 2,
+        interval_hours: 3.0,
+        cost: 4.0,
+    },
+    SupplierOffer {
+        good: BoxType::Grapes,
+        quantity: 2,
+        interval_hours: 4.0,
+        cost: 4.0,
+    },
+    SupplierOffer {
+        good: BoxType::Honey,
+        quantity: 1,
+        interval_hours: 3.0,
+        cost: 3.0,
+    },
+    SupplierOffer {
+        good: BoxType::Wheat,
+        quantity: 2,
+        interval_hours: 3.0,
+        cost: 4.0,
+    },
+];
+
+impl SupplierOffer {
+    /// The number of game seconds between deliveries
+    pub fn interval_seconds(&self) -> f64 {
+        (self.interval_hours / TIME_OF_DAY_HOURS_PER_GAME_SECONDS) as f64
+    }
+}
+
+/// A standing order that has been placed with a supplier
+#[derive(Clone, Debug)]
+pub struct SupplierOrder {
+    pub offer: SupplierOffer,
+
+    /// The time (since startup) the next delivery is added to the cart queue
+    pub due_at: f64,
+
+    /// The seconds that were left until the next delivery when the order was paused
+    pub paused_with: Option<f64>,
+}
+
+impl SupplierOrder {
+    pub fn is_paused(&self) -> bool {
+        self.paused_with.is_some()
+    }
+
+    pub fn is_due(&self, now: f64) -> bool {
+        !self.is_paused() && now >= self.due_at
+    }
+}
+
+/// A resource holding the standing orders placed with suppliers
+#[derive(Default, Debug)]
+pub struct SupplierOrders {
+    pub orders: Vec<SupplierOrder>,
+}
+
+impl SupplierOrders {
+    pub fn get(&self, good: BoxType) -> Option<&SupplierOrder> {
+        self.orders.iter().find(|order| order.offer.good == good)
+    }
+
+    /// Places a standing order for the given offer, replacing any existing order for that good
+    pub fn place(&mut self, offer: SupplierOffer, now: f64) {
+        self.cancel(offer.good);
+        self.orders.push(SupplierOrder {
+            offer,
+            due_at: now + offer.interval_seconds(),
+            paused_with: None,
+        });
+    }
+
+    pub fn cancel(&mut self, good: BoxType) {
+        self.orders.retain(|order| order.offer.good != good);
+    }
+
+    /// Pauses a running order, or resumes a paused one where it left off
+    pub fn toggle_paused(&mut self, good: BoxType, now: f64) {
+        if let Some(order) = self
+            .orders
+            .iter_mut()
+            .find(|order| order.offer.good == good)
+        {
+            match order.paused_with.take() {
+                Some(remaining) => order.due_at = now + remaining,
+                None => order.paused_with = Some((order.due_at - now).max(0.0)),
+            }
+        }
+    }
+}
+
+/// Adds goods from running standing orders to the cart delivery queue when they fall due
+pub fn deliver_supplier_orders(
+    time: Res<GameClock>,
+    mut supplier_orders: ResMut<SupplierOrders>,
+    mut spawning_state: ResMut<CartSpawningState>,
+    mut delivery_events: EventWriter<OnSupplierDelivery>,
+) {
+    let now = time.seconds_since_startup();
+
+    // check before mutating so the supplier panel only redraws when orders change
+    if !supplier_orders.orders.iter().any(|order| order.is_due(now)) {
+        return;
+    }
+
+    for order in supplier_orders.orders.iter_mut() {
+        if !order.is_due(now) {
+            continue;
+        }
+
+        order.due_at += order.offer.interval_seconds();
+        spawning_state
+            .items
+            .extend(vec![order.offer.good; order.offer.quantity]);
+
+        delivery_events.send(OnSupplierDelivery {
+            good: order.offer.good,
+            quantity: order.offer.quantity,
+            cost: order.offer.cost,
+        });
+    }
+}
+
+pub fn reset_supplier_orders(mut supplier_orders: ResMut<SupplierOrders>) {
+    supplier_orders.orders.clear();
+}
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        calendar::OnCalendarEventStarted,
+        clock::GameClock,
+        components::{CalendarBanner, WorldEntity},
+    },
+    loader::FontAssets,
+};
+
+/// How long a calendar event banner is shown for
+const BANNER_SECONDS: f32 = 6.0;
+
+/// Announces calendar events with a banner across the top of the screen
+pub fn spawn_calendar_banner(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    mut started_events: EventReader<OnCalendarEventStarted>,
+    banners: Query<Entity, With<CalendarBanner>>,
+) {
+    // only the latest event is announced if several start at once
+    let event = match started_events.iter().last() {
+        Some(evt) => &evt.0,
+        None => return,
+    };
+
+    for banner in banners.iter() {
+        commands.entity(banner).despawn_recursive();
+    }
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Percent(25.0),
+                    top: Val::Px(140.0),
+                    ..default()
+                },
+                size: Size::new(Val::Percent(50.0), Val::Auto),
+                padding: UiRect::all(Val::Px(10.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::rgba(0.15, 0.15, 0.15, 0.75).into(),
+            ..default()
+        })
+        .insert(CalendarBanner {
+            timer: Timer::from_seconds(BANNER_SECONDS, false),
+        })
+        .insert(WorldEntity)
+        .with_children(|banner| {
+            banner.spawn_bundle(TextBundle {
+                text: Text::from_section(
+                    format!("{} - {}", event.date.format("%e %B"), event.title),
+                    TextStyle {
+                        font: fonts.default_font.clone(),
+                        font_size: 28.0,
+                        color: Color::rgb(1.0, 0.85, 0.3),
+                    },
+                ),
+                ..default()
+            });
+
+            banner.spawn_bundle(TextBundle {
+                text: Text::from_section(
+                    event.description.clone(),
+                    TextStyle {
+                        font: fonts.default_font.clone(),
+                        font_size: 18.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..default()
+            });
+        });
+}
+
+pub fn despawn_calendar_banner(
+    mut commands: Commands,
+    time: Res<GameClock>,
+    mut banners: Query<(Entity, &mut CalendarBanner)>,
+) {
+    for (entity, mut banner) in banners.iter_mut() {
+        if banner.timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -1,49 +1,154 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::FocusPolicy};
+use leafwing_input_manager::prelude::ActionState;
 
 use crate::{
     game::{
-        components::{CartQueueUi, CartQueueUiItem},
-        spawners::CartSpawningState,
+        clock::GameClock,
+        components::{CartQueueUi, CartQueueUiEta, CartQueueUiItem},
+        spawners::{CartSpawningState, CartTypes},
     },
-    loader::TextureAssets,
+    input::{MousePosition, PlayerActions},
+    loader::{FontAssets, TextureAssets},
 };
 
+/// How far the mouse can move between pressing and releasing on a queued item for it to count
+/// as a click, in pixels
+const CLICK_TOLERANCE: f32 = 4.0;
+
+/// Rebuilds the cart delivery queue whenever the queued items change
 pub fn update_cart_request_queue(
     mut commands: Commands,
     textures: Res<TextureAssets>,
+    fonts: Res<FontAssets>,
     cart_spawn: Res<CartSpawningState>,
-    parent_query: Query<Entity, With<CartQueueUi>>,
-    mut queue_items: Query<(Entity, &mut UiImage), With<CartQueueUiItem>>,
+    parent_query: Query<(Entity, Option<&Children>), With<CartQueueUi>>,
+    queue_items: Query<(Entity, &CartQueueUiItem)>,
 ) {
-    let mut ui_items = queue_items.iter_mut().collect::<Vec<_>>();
-    let num_ui_items = ui_items.len();
+    if !cart_spawn.is_changed() {
+        return;
+    }
+
+    // the spawning state also changes as carts come and go, which doesn't affect the queue
+    let (parent, children) = parent_query.single();
+    let shown_ids = children
+        .into_iter()
+        .flat_map(|children| children.iter())
+        .filter_map(|child| queue_items.get(*child).ok())
+        .map(|(_, item)| item.0)
+        .collect::<Vec<_>>();
+    if shown_ids == cart_spawn.items.ids() {
+        return;
+    }
 
-    let num_queue_items = cart_spawn.items.len();
+    for (item, _) in queue_items.iter() {
+        commands.entity(item).despawn_recursive();
+    }
 
-    let parent = parent_query.single();
+    let eta_style = TextStyle {
+        font: fonts.default_font.clone(),
+        font_size: 12.0,
+        color: Color::WHITE,
+    };
 
-    // loop through all the items and updated/add/delete accordingly
-    #[allow(clippy::needless_range_loop)] // I think this is required because of the spawn part
-    for idx in 0..(num_ui_items.max(num_queue_items)) {
-        // create
-        if idx >= num_ui_items {
-            commands.entity(parent).with_children(|p| {
-                p.spawn_bundle(ImageBundle {
-                    image: cart_spawn.items[idx].get_image(&textures).into(),
+    commands.entity(parent).with_children(|parent| {
+        let queue = cart_spawn.items.iter().zip(cart_spawn.items.ids());
+        for (idx, (item, id)) in queue.enumerate() {
+            parent
+                .spawn_bundle(ButtonBundle {
+                    color: Color::NONE.into(),
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
                     ..default()
                 })
-                .insert(CartQueueUiItem);
-            });
-            continue;
+                .insert(CartQueueUiItem(*id))
+                .with_children(|button| {
+                    button.spawn_bundle(ImageBundle {
+                        image: item.get_image(&textures).into(),
+                        focus_policy: FocusPolicy::Pass,
+                        ..default()
+                    });
+
+                    button
+                        .spawn_bundle(TextBundle {
+                            text: Text::from_section("", eta_style.clone()),
+                            focus_policy: FocusPolicy::Pass,
+                            ..default()
+                        })
+                        .insert(CartQueueUiEta(idx));
+                });
         }
+    });
+}
+
+/// Shows how long until each queued item should arrive in a loading bay
+pub fn update_cart_request_etas(
+    time: Res<GameClock>,
+    cart_types: Res<CartTypes>,
+    cart_spawn: Res<CartSpawningState>,
+    mut eta_texts: Query<(&CartQueueUiEta, &mut Text)>,
+) {
+    let arrivals =
+        cart_spawn.estimated_arrivals(&cart_types, time.time_since_startup().as_secs_f32());
+
+    for (eta, mut text) in eta_texts.iter_mut() {
+        let value = match arrivals.get(eta.0) {
+            Some(seconds) => format!("{:.0}s ", seconds.ceil()),
+            None => String::new(),
+        };
 
-        // delete
-        if idx >= num_queue_items {
-            commands.entity(ui_items[idx].0).despawn_recursive();
-            continue;
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
         }
+    }
+}
+
+/// Lets the player edit the cart delivery queue. Clicking a queued item cancels it, and
+/// dragging an item onto another moves it to that position in the queue. Items are followed
+/// by id, so the drag survives the queue changing underneath it.
+pub fn edit_cart_request_queue(
+    mouse_pos: Res<MousePosition>,
+    mut cart_spawn: ResMut<CartSpawningState>,
+    mut dragging: Local<Option<(u64, Vec2)>>,
+    action_state_query: Query<&ActionState<PlayerActions>>,
+    changed_items: Query<(&Interaction, &CartQueueUiItem), Changed<Interaction>>,
+    items: Query<(&Interaction, &CartQueueUiItem)>,
+) {
+    for (interaction, item) in changed_items.iter() {
+        if *interaction == Interaction::Clicked {
+            *dragging = Some((item.0, mouse_pos.screen));
+        }
+    }
+
+    let action_state = action_state_query.single();
+    if !action_state.just_released(PlayerActions::Click) {
+        return;
+    }
+
+    let (dragged_id, pressed_at) = match dragging.take() {
+        Some(dragging) => dragging,
+        None => return,
+    };
+    let from = match cart_spawn.items.position(dragged_id) {
+        Some(from) => from,
+        None => return,
+    };
+
+    // when the mouse is released the item under the cursor becomes hovered
+    let to = match items
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Hovered)
+        .and_then(|(_, item)| cart_spawn.items.position(item.0))
+    {
+        Some(to) => to,
+        None => return,
+    };
 
-        // update
-        *ui_items[idx].1 = cart_spawn.items[idx].get_image(&textures).into();
+    if from != to {
+        cart_spawn.items.move_item(from, to);
+    } else if mouse_pos.screen.distance(pressed_at) <= CLICK_TOLERANCE {
+        let item = cart_spawn.items.remove(from);
+        info!("Cancelled queued delivery of {:?}", item);
     }
 }
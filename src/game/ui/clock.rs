@@ -0,0 +1,113 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    game::{
+        clock::{GameClock, CLOCK_SPEEDS},
+        components::{ClockButton, WorldEntity},
+    },
+    loader::FontAssets,
+};
+
+const ACTIVE_BUTTON_TEXT: Color = Color::rgb(1.0, 0.85, 0.3);
+
+pub fn spawn_clock_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    let text_style = TextStyle {
+        font: fonts.default_font.clone(),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    right: Val::Px(10.0),
+                    top: Val::Px(88.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(5.0)),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::rgba(0.15, 0.15, 0.15, 0.35).into(),
+            ..default()
+        })
+        .insert(WorldEntity)
+        .with_children(|panel| {
+            let buttons = std::iter::once((ClockButton::Pause, "Pause".to_string())).chain(
+                CLOCK_SPEEDS
+                    .iter()
+                    .map(|speed| (ClockButton::Speed(*speed), format!("{}x", speed))),
+            );
+
+            for (clock_button, label) in buttons {
+                panel
+                    .spawn_bundle(ButtonBundle {
+                        color: Color::NONE.into(),
+                        style: Style {
+                            padding: UiRect::new(
+                                Val::Px(5.0),
+                                Val::Px(5.0),
+                                Val::Undefined,
+                                Val::Undefined,
+                            ),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .insert(clock_button)
+                    .with_children(|button| {
+                        button.spawn_bundle(TextBundle {
+                            text: Text::from_section(label, text_style.clone()),
+                            focus_policy: FocusPolicy::Pass,
+                            ..default()
+                        });
+                    });
+            }
+        });
+}
+
+/// Highlights the button for the current speed, or the pause button while paused
+pub fn update_clock_ui(
+    clock: Res<GameClock>,
+    buttons: Query<(&ClockButton, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    for (clock_button, children) in buttons.iter() {
+        let is_active = match clock_button {
+            ClockButton::Pause => clock.is_paused(),
+            ClockButton::Speed(speed) => !clock.is_paused() && clock.speed() == *speed,
+        };
+        let colour = if is_active {
+            ACTIVE_BUTTON_TEXT
+        } else {
+            Color::WHITE
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                if text.sections[0].style.color != colour {
+                    text.sections[0].style.color = colour;
+                }
+            }
+        }
+    }
+}
+
+/// Pauses the game or changes its speed when the clock buttons are clicked
+pub fn handle_clock_buttons(
+    mut clock: ResMut<GameClock>,
+    buttons: Query<(&Interaction, &ClockButton), Changed<Interaction>>,
+) {
+    for (interaction, clock_button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        match clock_button {
+            ClockButton::Pause => clock.toggle_pause(),
+            ClockButton::Speed(speed) => clock.set_speed(*speed),
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 use iyes_loopless::prelude::IntoConditionalSystem;
 
-use crate::{game::components::CountDownTimer, loader::TextureAssets, GameState};
+use crate::{
+    game::clock::GameClock, game::components::CountDownTimer, loader::TextureAssets, GameState,
+};
 
 pub struct CountDownTimerPlugin;
 
@@ -12,7 +14,7 @@ impl Plugin for CountDownTimerPlugin {
     }
 }
 
-fn update_countdown_timers(time: Res<Time>, mut timers: Query<&mut CountDownTimer>) {
+fn update_countdown_timers(time: Res<GameClock>, mut timers: Query<&mut CountDownTimer>) {
     let delta = time.delta();
     for mut timer in timers.iter_mut() {
         timer.0.tick(delta);
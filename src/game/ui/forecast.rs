@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use chrono::Duration;
+
+use crate::{
+    game::{
+        components::{ForecastUi, WorldEntity},
+        weather::WeatherForecast,
+    },
+    loader::FontAssets,
+};
+
+pub fn spawn_forecast_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    right: Val::Px(10.0),
+                    top: Val::Px(52.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(5.0)),
+                ..default()
+            },
+            color: Color::rgba(0.15, 0.15, 0.15, 0.35).into(),
+            ..default()
+        })
+        .insert(WorldEntity)
+        .with_children(|panel| {
+            panel
+                .spawn_bundle(TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: fonts.default_font.clone(),
+                            font_size: 16.0,
+                            color: Color::WHITE,
+                        },
+                    ),
+                    ..default()
+                })
+                .insert(ForecastUi);
+        });
+}
+
+/// Shows the weather for today and the days ahead, so players can prepare for storms
+pub fn update_forecast_ui(
+    forecast: Res<WeatherForecast>,
+    mut texts: Query<&mut Text, With<ForecastUi>>,
+) {
+    if !forecast.is_changed() {
+        return;
+    }
+
+    for mut text in texts.iter_mut() {
+        let style = text.sections[0].style.clone();
+
+        text.sections = forecast
+            .days
+            .iter()
+            .enumerate()
+            .map(|(idx, weather)| {
+                let date = forecast.date + Duration::days(idx as i64);
+
+                TextSection {
+                    value: format!("{} {}  ", date.format("%a"), weather.label()),
+                    style: TextStyle {
+                        color: weather.colour(),
+                        ..style.clone()
+                    },
+                }
+            })
+            .collect();
+    }
+}
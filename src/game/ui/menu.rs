@@ -3,17 +3,25 @@ use iyes_loopless::{
     prelude::{AppLooplessStateExt, ConditionHelpers, IntoConditionalSystem},
     state::NextState,
 };
+use leafwing_input_manager::prelude::ActionState;
 
 use crate::{
     game::{
-        components::MenuItem,
+        components::{GameModeText, MenuItem},
         day_night_cycle::{get_start_date, TimeOfDay},
+        game_mode::GameMode,
+        ledger::Ledger,
+        save::{load_game, save_exists, PendingRestore},
     },
+    input::PlayerActions,
     loader::{FontAssets, TextureAssets},
     GameState,
 };
 
-use super::tutorial::was_action_pressed;
+use super::tutorial::{was_action_pressed, CurrentTutorialLevel};
+
+/// The number of ledger entries to show on the game over screen
+const GAME_OVER_LEDGER_ENTRIES: usize = 5;
 
 pub struct MenuPlugin;
 
@@ -23,6 +31,16 @@ impl Plugin for MenuPlugin {
             .add_exit_system(GameState::Menu, despawn_menu)
             .add_enter_system(GameState::GameOver, spawn_game_over)
             .add_exit_system(GameState::GameOver, despawn_game_over)
+            .add_system(
+                cycle_game_mode
+                    .run_in_state(GameState::Menu)
+                    .run_if(was_change_mode_pressed),
+            )
+            .add_system(
+                continue_saved_game
+                    .run_in_state(GameState::Menu)
+                    .run_if(was_continue_pressed),
+            )
             .add_system(
                 start_playing
                     .run_if(was_action_pressed)
@@ -32,13 +50,98 @@ impl Plugin for MenuPlugin {
     }
 }
 
-fn spawn_menu(mut commands: Commands, textures: Res<TextureAssets>) {
+fn spawn_menu(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    fonts: Res<FontAssets>,
+    textures: Res<TextureAssets>,
+) {
     commands
         .spawn_bundle(SpriteBundle {
             texture: textures.menu.clone(),
             ..default()
         })
-        .insert(MenuItem);
+        .insert(MenuItem)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(Text2dBundle {
+                    text: Text::from_section(
+                        get_game_mode_text(&game_mode),
+                        TextStyle {
+                            color: Color::ANTIQUE_WHITE,
+                            font: fonts.default_font.clone(),
+                            font_size: 20.0,
+                        },
+                    )
+                    .with_alignment(TextAlignment::CENTER),
+                    transform: Transform::from_xyz(0.0, -320.0, 1.0),
+                    ..default()
+                })
+                .insert(GameModeText);
+
+            if save_exists() {
+                parent.spawn_bundle(Text2dBundle {
+                    text: Text::from_section(
+                        "Press C to continue your saved game",
+                        TextStyle {
+                            color: Color::ANTIQUE_WHITE,
+                            font: fonts.default_font.clone(),
+                            font_size: 20.0,
+                        },
+                    )
+                    .with_alignment(TextAlignment::CENTER),
+                    transform: Transform::from_xyz(0.0, -345.0, 1.0),
+                    ..default()
+                });
+            }
+        });
+}
+
+fn get_game_mode_text(game_mode: &GameMode) -> String {
+    format!("Game mode: {} (press M to change)", game_mode.name())
+}
+
+fn was_change_mode_pressed(action_states: Query<&ActionState<PlayerActions>>) -> bool {
+    let action_state = action_states.single();
+    action_state.just_pressed(PlayerActions::ChangeMode)
+}
+
+fn cycle_game_mode(
+    mut game_mode: ResMut<GameMode>,
+    mut texts: Query<&mut Text, With<GameModeText>>,
+) {
+    *game_mode = game_mode.next();
+    info!("Changed game mode to {:?}", *game_mode);
+
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = get_game_mode_text(&game_mode);
+    }
+}
+
+fn was_continue_pressed(action_states: Query<&ActionState<PlayerActions>>) -> bool {
+    let action_state = action_states.single();
+    action_state.just_pressed(PlayerActions::Continue)
+}
+
+/// Loads the saved game and starts playing it. The game mode and tutorial level are set
+/// straight away, as they change how the world is set up.
+fn continue_saved_game(
+    mut commands: Commands,
+    mut game_mode: ResMut<GameMode>,
+    mut tutorial_level: ResMut<CurrentTutorialLevel>,
+) {
+    let save = match load_game() {
+        Ok(save) => save,
+        Err(e) => {
+            warn!("Unable to load saved game: {}", e);
+            return;
+        }
+    };
+
+    *game_mode = save.game_mode;
+    tutorial_level.0 = save.tutorial_level;
+    commands.insert_resource(PendingRestore(save));
+    commands.insert_resource(NextState(GameState::Playing));
 }
 
 fn despawn_menu(mut commands: Commands, menu_items: Query<Entity, With<MenuItem>>) {
@@ -54,10 +157,16 @@ fn start_playing(mut commands: Commands) {
 fn spawn_game_over(
     mut commands: Commands,
     time_of_day: Res<TimeOfDay>,
+    ledger: Res<Ledger>,
     fonts: Res<FontAssets>,
     textures: Res<TextureAssets>,
 ) {
     let elapsed_days = time_of_day.today - get_start_date();
+    let ledger_style = TextStyle {
+        color: Color::ANTIQUE_WHITE,
+        font: fonts.default_font.clone(),
+        font_size: 16.0,
+    };
 
     commands
         .spawn_bundle(SpriteBundle {
@@ -79,6 +188,30 @@ fn spawn_game_over(
                 transform: Transform::from_xyz(0.0, -32.0, 1.0),
                 ..default()
             });
+
+            // show the most recent losses and gains from the ledger
+            for (idx, entry) in ledger
+                .entries
+                .iter()
+                .rev()
+                .take(GAME_OVER_LEDGER_ENTRIES)
+                .enumerate()
+            {
+                parent.spawn_bundle(Text2dBundle {
+                    text: Text::from_section(
+                        format!(
+                            "{} - {}: {:.0}",
+                            entry.date.format("%-d %B"),
+                            entry.description,
+                            entry.amount
+                        ),
+                        ledger_style.clone(),
+                    )
+                    .with_alignment(TextAlignment::CENTER),
+                    transform: Transform::from_xyz(0.0, -64.0 - 20.0 * idx as f32, 1.0),
+                    ..default()
+                });
+            }
         });
 }
 
@@ -1,19 +1,29 @@
 mod button_interaction;
+mod calendar_banner;
 mod cart_request;
+mod clock;
 mod countdown_timer;
 mod factory;
+mod forecast;
 mod game_ui_bar;
 mod menu;
+mod night_shift;
 mod production_queue;
+mod schedule;
 mod score;
 mod ship_demand;
+mod suppliers;
 pub mod tutorial;
+mod warehouse;
 pub use score::{OnCoinsReceived, Score};
 
 use bevy::prelude::*;
-use iyes_loopless::prelude::{AppLooplessStateExt, IntoConditionalSystem};
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionHelpers, IntoConditionalSystem};
 
-use crate::{game::ui::menu::MenuPlugin, GameState};
+use crate::{
+    game::{calendar::OnCalendarEventStarted, clock::is_not_paused, ui::menu::MenuPlugin},
+    GameState,
+};
 
 use self::{countdown_timer::CountDownTimerPlugin, score::OnShipScore, tutorial::TutorialPlugin};
 
@@ -33,6 +43,12 @@ impl Plugin for UiPlugin {
             .add_plugin(CountDownTimerPlugin)
             .add_enter_system(GameState::Playing, game_ui_bar::spawn_game_ui)
             .add_enter_system(GameState::Playing, factory::spawn_factory_ui)
+            .add_enter_system(GameState::Playing, schedule::spawn_schedule_ui)
+            .add_enter_system(GameState::Playing, suppliers::spawn_supplier_ui)
+            .add_enter_system(GameState::Playing, warehouse::spawn_warehouse_ui)
+            .add_enter_system(GameState::Playing, forecast::spawn_forecast_ui)
+            .add_enter_system(GameState::Playing, clock::spawn_clock_ui)
+            .add_enter_system(GameState::Playing, night_shift::spawn_night_shift_ui)
             .add_system(
                 score::score_display
                     .run_in_state(GameState::Playing)
@@ -44,6 +60,51 @@ impl Plugin for UiPlugin {
                     .run_in_state(GameState::Playing)
                     .before(SystemLabels::ScoreDisplay),
             )
+            .add_system(
+                score::penalise_foundered_ships
+                    .run_in_state(GameState::Playing)
+                    .before(SystemLabels::ScoreDisplay),
+            )
+            .add_system(
+                score::pay_for_imports
+                    .run_in_state(GameState::Playing)
+                    .before(SystemLabels::ScoreDisplay),
+            )
+            .add_system(
+                score::penalise_unclaimed_cart_crates
+                    .run_in_state(GameState::Playing)
+                    .before(SystemLabels::ScoreDisplay),
+            )
+            .add_system(
+                score::pay_for_conveyors
+                    .run_in_state(GameState::Playing)
+                    .before(SystemLabels::ScoreDisplay),
+            )
+            .add_system(
+                score::refund_conveyors
+                    .run_in_state(GameState::Playing)
+                    .before(SystemLabels::ScoreDisplay),
+            )
+            .add_system(
+                score::penalise_shattered_crates
+                    .run_in_state(GameState::Playing)
+                    .before(SystemLabels::ScoreDisplay),
+            )
+            .add_system(
+                score::pay_for_crane
+                    .run_in_state(GameState::Playing)
+                    .before(SystemLabels::ScoreDisplay),
+            )
+            .add_system(
+                score::pay_for_night_work
+                    .run_in_state(GameState::Playing)
+                    .before(SystemLabels::ScoreDisplay),
+            )
+            .add_system(
+                score::pay_for_supplier_deliveries
+                    .run_in_state(GameState::Playing)
+                    .before(SystemLabels::ScoreDisplay),
+            )
             .add_system(
                 score::despawn_ships_and_penalise
                     .run_in_state(GameState::Playing)
@@ -51,8 +112,41 @@ impl Plugin for UiPlugin {
             )
             .add_system(score::update_current_date.run_in_state(GameState::Playing))
             .add_system(cart_request::update_cart_request_queue.run_in_state(GameState::Playing))
+            .add_system(cart_request::update_cart_request_etas.run_in_state(GameState::Playing))
+            .add_system(
+                cart_request::edit_cart_request_queue
+                    .run_in_state(GameState::Playing)
+                    .run_if(is_not_paused),
+            )
             .add_system(ship_demand::remove_ship_demands_when_met.run_in_state(GameState::Playing))
             .add_system(production_queue::update_production_queue.run_in_state(GameState::Playing))
+            .add_system(schedule::update_schedule_ui.run_in_state(GameState::Playing))
+            .add_system(suppliers::update_supplier_ui.run_in_state(GameState::Playing))
+            .add_system(
+                suppliers::handle_supplier_buttons
+                    .run_in_state(GameState::Playing)
+                    .run_if(is_not_paused),
+            )
+            .add_system(warehouse::update_warehouse_ui.run_in_state(GameState::Playing))
+            .add_system(forecast::update_forecast_ui.run_in_state(GameState::Playing))
+            .add_system(
+                calendar_banner::spawn_calendar_banner.run_on_event::<OnCalendarEventStarted>(),
+            )
+            .add_system(calendar_banner::despawn_calendar_banner.run_in_state(GameState::Playing))
+            .add_system(night_shift::update_night_shift_ui.run_in_state(GameState::Playing))
+            .add_system(
+                night_shift::handle_night_shift_buttons
+                    .run_in_state(GameState::Playing)
+                    .run_if(is_not_paused),
+            )
+            .add_system(clock::update_clock_ui.run_in_state(GameState::Playing))
+            .add_system(clock::handle_clock_buttons.run_in_state(GameState::Playing))
+            .add_system(warehouse::update_crane_button.run_in_state(GameState::Playing))
+            .add_system(
+                warehouse::handle_crane_button
+                    .run_in_state(GameState::Playing)
+                    .run_if(is_not_paused),
+            )
             .add_system(factory::update_factory_input_ui.run_in_state(GameState::Playing));
     }
 }
@@ -0,0 +1,110 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    game::{
+        components::{NightShiftButton, NightWork, WorldEntity},
+        night::{NightShift, FACTORY_LAMPS_COST, NIGHT_SHIFT_COST},
+        ui::Score,
+    },
+    loader::FontAssets,
+};
+
+pub fn spawn_night_shift_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    let text_style = TextStyle {
+        font: fonts.default_font.clone(),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    right: Val::Px(10.0),
+                    top: Val::Px(124.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(5.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::FlexEnd,
+                ..default()
+            },
+            color: Color::rgba(0.15, 0.15, 0.15, 0.35).into(),
+            ..default()
+        })
+        .insert(WorldEntity)
+        .with_children(|panel| {
+            for work in [NightWork::Carts, NightWork::FactoryLamps] {
+                panel
+                    .spawn_bundle(ButtonBundle {
+                        color: Color::NONE.into(),
+                        ..default()
+                    })
+                    .insert(NightShiftButton(work))
+                    .with_children(|button| {
+                        button.spawn_bundle(TextBundle {
+                            text: Text::from_section("", text_style.clone()),
+                            focus_policy: FocusPolicy::Pass,
+                            ..default()
+                        });
+                    });
+            }
+        });
+}
+
+/// Shows whether each kind of night work is being paid for
+pub fn update_night_shift_ui(
+    night_shift: Res<NightShift>,
+    buttons: Query<(&NightShiftButton, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    for (button, children) in buttons.iter() {
+        let (label, hired, cost) = match button.0 {
+            NightWork::Carts => ("Night carts", night_shift.carts, NIGHT_SHIFT_COST),
+            NightWork::FactoryLamps => (
+                "Factory lamps",
+                night_shift.factory_lamps,
+                FACTORY_LAMPS_COST,
+            ),
+        };
+
+        let value = format!(
+            "{}: {} (${}/night)",
+            label,
+            if hired { "on" } else { "off" },
+            cost
+        );
+
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                if text.sections[0].value != value {
+                    text.sections[0].value = value.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Starts or stops paying for night work when the night shift buttons are clicked. Night work
+/// can't be started unless the company can afford a night of it.
+pub fn handle_night_shift_buttons(
+    score: Res<Score>,
+    mut night_shift: ResMut<NightShift>,
+    buttons: Query<(&Interaction, &NightShiftButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        let hired = night_shift.is_hired(button.0);
+        if !hired && score.0 < NightShift::cost(button.0) {
+            info!("Can't afford {:?}", button.0);
+            continue;
+        }
+
+        night_shift.set_hired(button.0, !hired);
+        info!("Night shift is now {:?}", *night_shift);
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        clock::GameClock,
+        components::{ShipScheduleUi, ShipScheduleUiRow, WorldEntity},
+        day_night_cycle::{TimeOfDay, TIME_OF_DAY_HOURS_PER_GAME_SECONDS},
+        spawners::ShipSchedule,
+    },
+    loader::{FontAssets, TextureAssets},
+};
+
+const SCHEDULE_ICON_SIZE: f32 = 24.0;
+
+pub fn spawn_schedule_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    right: Val::Px(10.0),
+                    top: Val::Px(100.0),
+                    ..default()
+                },
+                min_size: Size::new(Val::Px(160.0), Val::Auto),
+                padding: UiRect::all(Val::Px(5.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::FlexStart,
+                ..default()
+            },
+            color: Color::rgba(0.15, 0.15, 0.15, 0.35).into(),
+            ..default()
+        })
+        .insert(ShipScheduleUi)
+        .insert(WorldEntity)
+        .with_children(|schedule| {
+            schedule.spawn_bundle(TextBundle {
+                text: Text::from_section(
+                    "Ship schedule",
+                    TextStyle {
+                        font: fonts.default_font.clone(),
+                        font_size: 16.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..default()
+            });
+        });
+}
+
+/// Rebuilds the rows of the schedule panel whenever the ship schedule changes
+#[allow(clippy::too_many_arguments)]
+pub fn update_schedule_ui(
+    mut commands: Commands,
+    time: Res<GameClock>,
+    time_of_day: Res<TimeOfDay>,
+    fonts: Res<FontAssets>,
+    textures: Res<TextureAssets>,
+    schedule: Res<ShipSchedule>,
+    panels: Query<Entity, With<ShipScheduleUi>>,
+    rows: Query<Entity, With<ShipScheduleUiRow>>,
+) {
+    if !schedule.is_changed() {
+        return;
+    }
+
+    for row in rows.iter() {
+        commands.entity(row).despawn_recursive();
+    }
+
+    let panel = match panels.get_single() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let text_style = TextStyle {
+        font: fonts.default_font.clone(),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
+    let now = time.seconds_since_startup();
+
+    commands.entity(panel).with_children(|schedule_layout| {
+        for ship in schedule.ships.iter() {
+            let hours_until_arrival =
+                (ship.arrival - now).max(0.0) as f32 * TIME_OF_DAY_HOURS_PER_GAME_SECONDS;
+            let arrival_hour = (time_of_day.time_of_day + hours_until_arrival) % 24.0;
+
+            schedule_layout
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Auto, Val::Px(SCHEDULE_ICON_SIZE)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    color: Color::NONE.into(),
+                    ..default()
+                })
+                .insert(ShipScheduleUiRow)
+                .with_children(|row| {
+                    row.spawn_bundle(TextBundle {
+                        text: Text::from_section(
+                            format!(
+                                "{:02}:{:02}  ",
+                                arrival_hour.floor() as u32,
+                                (arrival_hour.fract() * 60.0).floor() as u32
+                            ),
+                            text_style.clone(),
+                        ),
+                        ..default()
+                    });
+
+                    for demand in ship.demands.iter() {
+                        row.spawn_bundle(ImageBundle {
+                            image: demand.get_image(&textures).into(),
+                            style: Style {
+                                size: Size::new(
+                                    Val::Px(SCHEDULE_ICON_SIZE),
+                                    Val::Px(SCHEDULE_ICON_SIZE),
+                                ),
+                                ..default()
+                            },
+                            ..default()
+                        });
+                    }
+
+                    if ship.imports.is_empty() {
+                        return;
+                    }
+
+                    // imports are shown after a "down" arrow
+                    for image in std::iter::once(textures.down.clone()).chain(
+                        ship.imports
+                            .iter()
+                            .map(|import| import.get_image(&textures)),
+                    ) {
+                        row.spawn_bundle(ImageBundle {
+                            image: image.into(),
+                            style: Style {
+                                size: Size::new(
+                                    Val::Px(SCHEDULE_ICON_SIZE),
+                                    Val::Px(SCHEDULE_ICON_SIZE),
+                                ),
+                                ..default()
+                            },
+                            ..default()
+                        });
+                    }
+                });
+        }
+    });
+}
@@ -4,15 +4,27 @@ use num_format::{Locale, ToFormattedString};
 
 use crate::{
     game::{
-        actions::OnDropCrateOnShip,
+        actions::{OnCrateShattered, OnDropCrateOnShip},
         animation::OnShipArrivedAtDestination,
         components::{CurrentDateMarker, ScoreUi, ShipHold, Wave},
+        conveyor::{OnConveyorPurchased, OnConveyorRemoved, CONVEYOR_SEGMENT_COST},
+        crane::{OnCranePurchased, OnCraneRepaired, CRANE_COST, CRANE_REPAIR_COST},
         day_night_cycle::TimeOfDay,
+        ledger::Ledger,
+        night::OnNightWorkPaid,
+        spawners::{OnCartLeft, OnImportsUnloaded, OnSupplierDelivery},
+        OnShipFoundered,
     },
     GameState,
 };
 
 pub const UNMET_DEMAND_PENALTY: f32 = 40.0;
+pub const LOST_CARGO_PENALTY: f32 = 15.0;
+pub const IMPORT_COST_PER_CRATE: f32 = 4.0;
+pub const UNCLAIMED_CRATE_PENALTY: f32 = 5.0;
+pub const ROTTEN_CRATE_PENALTY: f32 = 10.0;
+pub const SHATTERED_CRATE_PENALTY: f32 = 8.0;
+pub const WET_CRATE_VALUE: f32 = 5.0;
 
 /// Event triggered when a player receives coins, allowing effects to be played
 pub struct OnCoinsReceived;
@@ -33,12 +45,28 @@ pub fn score_display(score: Res<Score>, mut texts: Query<&mut Text, With<ScoreUi
 
 pub fn score_update(
     mut commands: Commands,
+    time_of_day: Res<TimeOfDay>,
     mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
     mut drop_on_ship_event: EventReader<OnDropCrateOnShip>,
     mut on_coin_drop: EventWriter<OnCoinsReceived>,
 ) {
     for evt in drop_on_ship_event.iter() {
-        let amt = if evt.was_demanded { 10.0 } else { -5.0 };
+        if evt.was_rotten {
+            score.0 -= ROTTEN_CRATE_PENALTY;
+            ledger.record(
+                time_of_day.today,
+                format!("Delivered rotten {:?}", evt.box_type),
+                -ROTTEN_CRATE_PENALTY,
+            );
+            continue;
+        }
+
+        let amt = match (evt.was_demanded, evt.was_wet) {
+            (true, false) => 10.0,
+            (true, true) => WET_CRATE_VALUE,
+            (false, _) => -5.0,
+        };
         score.0 += amt;
 
         if amt > 0.0 {
@@ -74,6 +102,159 @@ pub fn despawn_ships_and_penalise(
     }
 }
 
+pub fn penalise_foundered_ships(
+    time_of_day: Res<TimeOfDay>,
+    mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
+    mut founder_events: EventReader<OnShipFoundered>,
+) {
+    for evt in founder_events.iter() {
+        let penalty = evt.cargo.len() as f32 * LOST_CARGO_PENALTY;
+        score.0 -= penalty;
+
+        ledger.record(
+            time_of_day.today,
+            format!("Ship lost in a storm with {} crates", evt.cargo.len()),
+            -penalty,
+        );
+    }
+}
+
+pub fn pay_for_imports(
+    time_of_day: Res<TimeOfDay>,
+    mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
+    mut unload_events: EventReader<OnImportsUnloaded>,
+) {
+    for evt in unload_events.iter() {
+        let cost = evt.crates.len() as f32 * IMPORT_COST_PER_CRATE;
+        score.0 -= cost;
+
+        ledger.record(
+            time_of_day.today,
+            format!("Imported {} crates", evt.crates.len()),
+            -cost,
+        );
+    }
+}
+
+pub fn penalise_unclaimed_cart_crates(
+    time_of_day: Res<TimeOfDay>,
+    mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
+    mut cart_left_events: EventReader<OnCartLeft>,
+) {
+    for evt in cart_left_events.iter() {
+        if evt.unclaimed_crates == 0 {
+            continue;
+        }
+
+        let penalty = evt.unclaimed_crates as f32 * UNCLAIMED_CRATE_PENALTY;
+        score.0 -= penalty;
+
+        ledger.record(
+            time_of_day.today,
+            format!("Cart left with {} unclaimed crates", evt.unclaimed_crates),
+            -penalty,
+        );
+    }
+}
+
+pub fn pay_for_supplier_deliveries(
+    time_of_day: Res<TimeOfDay>,
+    mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
+    mut delivery_events: EventReader<OnSupplierDelivery>,
+) {
+    for evt in delivery_events.iter() {
+        score.0 -= evt.cost;
+
+        ledger.record(
+            time_of_day.today,
+            format!("Standing order for {} {:?}", evt.quantity, evt.good),
+            -evt.cost,
+        );
+    }
+}
+
+pub fn pay_for_conveyors(
+    time_of_day: Res<TimeOfDay>,
+    mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
+    mut purchase_events: EventReader<OnConveyorPurchased>,
+) {
+    for _ in purchase_events.iter() {
+        score.0 -= CONVEYOR_SEGMENT_COST;
+        ledger.record(
+            time_of_day.today,
+            "Bought a conveyor segment",
+            -CONVEYOR_SEGMENT_COST,
+        );
+    }
+}
+
+pub fn refund_conveyors(
+    time_of_day: Res<TimeOfDay>,
+    mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
+    mut removed_events: EventReader<OnConveyorRemoved>,
+) {
+    for _ in removed_events.iter() {
+        score.0 += CONVEYOR_SEGMENT_COST;
+        ledger.record(
+            time_of_day.today,
+            "Sold a conveyor segment",
+            CONVEYOR_SEGMENT_COST,
+        );
+    }
+}
+
+pub fn pay_for_crane(
+    time_of_day: Res<TimeOfDay>,
+    mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
+    mut purchase_events: EventReader<OnCranePurchased>,
+    mut repaired_events: EventReader<OnCraneRepaired>,
+) {
+    for _ in purchase_events.iter() {
+        score.0 -= CRANE_COST;
+        ledger.record(time_of_day.today, "Bought a dockside crane", -CRANE_COST);
+    }
+
+    for _ in repaired_events.iter() {
+        score.0 -= CRANE_REPAIR_COST;
+        ledger.record(time_of_day.today, "Crane repairs", -CRANE_REPAIR_COST);
+    }
+}
+
+pub fn pay_for_night_work(
+    time_of_day: Res<TimeOfDay>,
+    mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
+    mut paid_events: EventReader<OnNightWorkPaid>,
+) {
+    for evt in paid_events.iter() {
+        score.0 -= evt.cost;
+        ledger.record(time_of_day.today, evt.description, -evt.cost);
+    }
+}
+
+pub fn penalise_shattered_crates(
+    time_of_day: Res<TimeOfDay>,
+    mut score: ResMut<Score>,
+    mut ledger: ResMut<Ledger>,
+    mut shatter_events: EventReader<OnCrateShattered>,
+) {
+    for evt in shatter_events.iter() {
+        score.0 -= SHATTERED_CRATE_PENALTY;
+        ledger.record(
+            time_of_day.today,
+            format!("Broke a crate of {:?}", evt.box_type),
+            -SHATTERED_CRATE_PENALTY,
+        );
+    }
+}
+
 pub fn update_current_date(
     time_of_day: Res<TimeOfDay>,
     mut date_text: Query<&mut Text, With<CurrentDateMarker>>,
@@ -12,6 +12,10 @@ pub fn remove_ship_demands_when_met(
     demand_markers: Query<(Entity, &ShipDemandItemMarker)>,
 ) {
     for evt in drop_events.iter() {
+        if !evt.was_demanded {
+            continue;
+        }
+
         if let Ok(ship_children) = ships.get(evt.ship_entity) {
             for child_entity in ship_children.iter().rev() {
                 if let Ok((demand_entity, demand_item)) = demand_markers.get(*child_entity) {
@@ -0,0 +1,189 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    game::{
+        clock::GameClock,
+        components::{
+            SupplierAction, SupplierOrderButton, SupplierPanelRow, SupplierPanelUi, WorldEntity,
+        },
+        spawners::{suppliers::SUPPLIER_OFFERS, SupplierOrders},
+    },
+    loader::{FontAssets, TextureAssets},
+};
+
+const SUPPLIER_ICON_SIZE: f32 = 24.0;
+
+pub fn spawn_supplier_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Px(10.0),
+                    top: Val::Px(100.0),
+                    ..default()
+                },
+                min_size: Size::new(Val::Px(160.0), Val::Auto),
+                padding: UiRect::all(Val::Px(5.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::FlexStart,
+                ..default()
+            },
+            color: Color::rgba(0.15, 0.15, 0.15, 0.35).into(),
+            ..default()
+        })
+        .insert(SupplierPanelUi)
+        .insert(WorldEntity)
+        .with_children(|panel| {
+            panel.spawn_bundle(TextBundle {
+                text: Text::from_section(
+                    "Standing orders",
+                    TextStyle {
+                        font: fonts.default_font.clone(),
+                        font_size: 16.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..default()
+            });
+        });
+}
+
+/// Rebuilds the rows of the supplier panel whenever standing orders are placed or edited
+pub fn update_supplier_ui(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    textures: Res<TextureAssets>,
+    supplier_orders: Res<SupplierOrders>,
+    panels: Query<Entity, With<SupplierPanelUi>>,
+    rows: Query<Entity, With<SupplierPanelRow>>,
+) {
+    if !supplier_orders.is_changed() {
+        return;
+    }
+
+    for row in rows.iter() {
+        commands.entity(row).despawn_recursive();
+    }
+
+    let panel = match panels.get_single() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let text_style = TextStyle {
+        font: fonts.default_font.clone(),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
+
+    commands.entity(panel).with_children(|panel_layout| {
+        for offer in SUPPLIER_OFFERS.iter() {
+            let order = supplier_orders.get(offer.good);
+
+            panel_layout
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Auto, Val::Px(SUPPLIER_ICON_SIZE)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    color: Color::NONE.into(),
+                    ..default()
+                })
+                .insert(SupplierPanelRow)
+                .with_children(|row| {
+                    row.spawn_bundle(ImageBundle {
+                        image: offer.good.get_image(&textures).into(),
+                        style: Style {
+                            size: Size::new(
+                                Val::Px(SUPPLIER_ICON_SIZE),
+                                Val::Px(SUPPLIER_ICON_SIZE),
+                            ),
+                            ..default()
+                        },
+                        ..default()
+                    });
+
+                    row.spawn_bundle(TextBundle {
+                        text: Text::from_section(
+                            format!(
+                                " x{} every {}h (${})  ",
+                                offer.quantity, offer.interval_hours, offer.cost
+                            ),
+                            text_style.clone(),
+                        ),
+                        ..default()
+                    });
+
+                    let buttons = match order {
+                        None => vec![("Order", SupplierAction::Place)],
+                        Some(order) if order.is_paused() => vec![
+                            ("Resume", SupplierAction::TogglePause),
+                            ("Cancel", SupplierAction::Cancel),
+                        ],
+                        Some(_) => vec![
+                            ("Pause", SupplierAction::TogglePause),
+                            ("Cancel", SupplierAction::Cancel),
+                        ],
+                    };
+
+                    for (label, action) in buttons {
+                        row.spawn_bundle(ButtonBundle {
+                            color: Color::NONE.into(),
+                            style: Style {
+                                padding: UiRect::new(
+                                    Val::Px(4.0),
+                                    Val::Px(4.0),
+                                    Val::Px(0.0),
+                                    Val::Px(0.0),
+                                ),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .insert(SupplierOrderButton {
+                            good: offer.good,
+                            action,
+                        })
+                        .with_children(|button| {
+                            button.spawn_bundle(TextBundle {
+                                text: Text::from_section(label, text_style.clone()),
+                                focus_policy: FocusPolicy::Pass,
+                                ..default()
+                            });
+                        });
+                    }
+                });
+        }
+    });
+}
+
+/// Places, pauses and cancels standing orders when the supplier panel buttons are clicked
+pub fn handle_supplier_buttons(
+    time: Res<GameClock>,
+    mut supplier_orders: ResMut<SupplierOrders>,
+    buttons: Query<(&Interaction, &SupplierOrderButton), Changed<Interaction>>,
+) {
+    let now = time.seconds_since_startup();
+
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        match button.action {
+            SupplierAction::Place => {
+                if let Some(offer) = SUPPLIER_OFFERS.iter().find(|o| o.good == button.good) {
+                    info!("Placed standing order for {:?}", button.good);
+                    supplier_orders.place(*offer, now);
+                }
+            }
+            SupplierAction::TogglePause => supplier_orders.toggle_paused(button.good, now),
+            SupplierAction::Cancel => {
+                info!("Cancelled standing order for {:?}", button.good);
+                supplier_orders.cancel(button.good);
+            }
+        }
+    }
+}
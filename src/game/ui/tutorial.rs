@@ -17,6 +17,10 @@ use crate::{
     GameState,
 };
 
+/// The tutorial level reached once the player has finished the tutorial, when the game
+/// proper begins
+pub const TUTORIAL_COMPLETE_LEVEL: u8 = 9;
+
 /// A resource which holds the current tutorial level
 pub struct CurrentTutorialLevel(pub u8);
 
@@ -226,5 +230,5 @@ fn level8_tutorial(
     }
 
     // go to the next tutorial level, here a ship will be spawned and tutorial level 6 will be set
-    tutorial.0 = 9;
+    tutorial.0 = TUTORIAL_COMPLETE_LEVEL;
 }
@@ -0,0 +1,210 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    game::{
+        components::{CraneButton, WarehouseUi, WarehouseUiRow, WarehouseUiTitle, WorldEntity},
+        crane::{Crane, OnCranePurchased, CRANE_COST, CRANE_REPAIR_COST},
+        ui::Score,
+        warehouse::Warehouse,
+    },
+    loader::{FontAssets, TextureAssets},
+};
+
+const WAREHOUSE_ICON_SIZE: f32 = 24.0;
+
+pub fn spawn_warehouse_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Px(10.0),
+                    top: Val::Px(270.0),
+                    ..default()
+                },
+                min_size: Size::new(Val::Px(160.0), Val::Auto),
+                padding: UiRect::all(Val::Px(5.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::FlexStart,
+                ..default()
+            },
+            color: Color::rgba(0.15, 0.15, 0.15, 0.35).into(),
+            ..default()
+        })
+        .insert(WarehouseUi)
+        .insert(WorldEntity)
+        .with_children(|panel| {
+            let text_style = TextStyle {
+                font: fonts.default_font.clone(),
+                font_size: 16.0,
+                color: Color::WHITE,
+            };
+
+            panel
+                .spawn_bundle(TextBundle {
+                    text: Text::from_section("Warehouse", text_style.clone()),
+                    ..default()
+                })
+                .insert(WarehouseUiTitle);
+
+            panel
+                .spawn_bundle(ButtonBundle {
+                    color: Color::NONE.into(),
+                    ..default()
+                })
+                .with_children(|button| {
+                    button
+                        .spawn_bundle(TextBundle {
+                            text: Text::from_section("", text_style),
+                            focus_policy: FocusPolicy::Pass,
+                            ..default()
+                        })
+                        .insert(CraneButton);
+                });
+        });
+}
+
+/// Shows whether the crane can be bought, or what it is doing. The buy button is greyed out
+/// while the company can't afford the crane.
+pub fn update_crane_button(
+    crane: Res<Crane>,
+    score: Res<Score>,
+    mut texts: Query<&mut Text, With<CraneButton>>,
+) {
+    let colour = if !crane.owned && score.0 < CRANE_COST {
+        Color::GRAY
+    } else {
+        Color::WHITE
+    };
+
+    let value = if !crane.owned {
+        format!("Buy crane (${})", CRANE_COST)
+    } else if let Some(repair) = crane.repair.as_ref() {
+        if repair.finished() {
+            format!("Crane: broken (repairs ${})", CRANE_REPAIR_COST)
+        } else {
+            format!(
+                "Crane: broken ({:.0}s)",
+                (repair.duration() - repair.elapsed()).as_secs_f32().ceil()
+            )
+        }
+    } else if crane.load.is_some() {
+        "Crane: loading".to_string()
+    } else {
+        "Crane: waiting".to_string()
+    };
+
+    for mut text in texts.iter_mut() {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+
+        if text.sections[0].style.color != colour {
+            text.sections[0].style.color = colour;
+        }
+    }
+}
+
+/// Buys the crane when its button is clicked, if the company can afford it
+#[allow(clippy::type_complexity)]
+pub fn handle_crane_button(
+    score: Res<Score>,
+    mut crane: ResMut<Crane>,
+    mut purchase_events: EventWriter<OnCranePurchased>,
+    buttons: Query<(&Interaction, &Children), (Changed<Interaction>, With<Button>)>,
+    crane_texts: Query<(), With<CraneButton>>,
+) {
+    for (interaction, children) in buttons.iter() {
+        if *interaction != Interaction::Clicked
+            || crane.owned
+            || score.0 < CRANE_COST
+            || !children.iter().any(|child| crane_texts.get(*child).is_ok())
+        {
+            continue;
+        }
+
+        info!("Bought a crane");
+        crane.owned = true;
+        purchase_events.send(OnCranePurchased);
+    }
+}
+
+/// Rebuilds the warehouse inventory panel whenever crates arrive or leave
+pub fn update_warehouse_ui(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    textures: Res<TextureAssets>,
+    warehouse: Res<Warehouse>,
+    panels: Query<Entity, With<WarehouseUi>>,
+    rows: Query<Entity, With<WarehouseUiRow>>,
+    mut titles: Query<&mut Text, With<WarehouseUiTitle>>,
+) {
+    if !warehouse.is_changed() {
+        return;
+    }
+
+    for mut title in titles.iter_mut() {
+        title.sections[0].value = format!(
+            "Warehouse ({}/{})",
+            warehouse.crates.len(),
+            warehouse.capacity
+        );
+        title.sections[0].style.color = if warehouse.crates.len() >= warehouse.capacity {
+            Color::ORANGE_RED
+        } else {
+            Color::WHITE
+        };
+    }
+
+    for row in rows.iter() {
+        commands.entity(row).despawn_recursive();
+    }
+
+    let panel = match panels.get_single() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let text_style = TextStyle {
+        font: fonts.default_font.clone(),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
+
+    commands.entity(panel).with_children(|panel_layout| {
+        for good in warehouse.goods() {
+            panel_layout
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Auto, Val::Px(WAREHOUSE_ICON_SIZE)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    color: Color::NONE.into(),
+                    ..default()
+                })
+                .insert(WarehouseUiRow)
+                .with_children(|row| {
+                    row.spawn_bundle(ImageBundle {
+                        image: good.get_image(&textures).into(),
+                        style: Style {
+                            size: Size::new(
+                                Val::Px(WAREHOUSE_ICON_SIZE),
+                                Val::Px(WAREHOUSE_ICON_SIZE),
+                            ),
+                            ..default()
+                        },
+                        ..default()
+                    });
+
+                    row.spawn_bundle(TextBundle {
+                        text: Text::from_section(
+                            format!(" x{}", warehouse.count(good)),
+                            text_style.clone(),
+                        ),
+                        ..default()
+                    });
+                });
+        }
+    });
+}
@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use heron::Velocity;
+use iyes_loopless::prelude::{AppLooplessStateExt, IntoConditionalSystem};
+
+use crate::{
+    game::{
+        components::{BoxType, PhysicsCrate, ToppledCrate},
+        WAREHOUSE_LOCATION,
+    },
+    GameState, GRID_SIZE,
+};
+
+/// Half the width and height of the warehouse floor collider
+pub const WAREHOUSE_HALF_EXTENTS: Vec2 = Vec2::new(103.0, 26.0);
+
+/// How high above the warehouse floor crates are counted as being stored
+const WAREHOUSE_STORAGE_HEIGHT: f32 = 4.0 * GRID_SIZE;
+
+const WAREHOUSE_CAPACITY: usize = 6;
+
+/// How hard crates that don't fit are pushed off the warehouse
+const TOPPLE_VELOCITY: Vec2 = Vec2::new(150.0, 200.0);
+
+/// Event triggered when a crate is pushed off a full warehouse
+pub struct OnWarehouseOverflow;
+
+/// A resource tracking the crates stacked in the warehouse
+#[derive(Debug)]
+pub struct Warehouse {
+    pub capacity: usize,
+
+    /// The crates in the warehouse, in the order they arrived
+    pub crates: Vec<(Entity, BoxType)>,
+}
+
+impl Default for Warehouse {
+    fn default() -> Self {
+        Self {
+            capacity: WAREHOUSE_CAPACITY,
+            crates: vec![],
+        }
+    }
+}
+
+impl Warehouse {
+    /// The number of crates of the given type in the warehouse
+    pub fn count(&self, box_type: BoxType) -> usize {
+        self.crates.iter().filter(|(_, bt)| *bt == box_type).count()
+    }
+
+    /// The types of crate in the warehouse, in the order they first arrived
+    pub fn goods(&self) -> Vec<BoxType> {
+        let mut goods = vec![];
+        for (_, box_type) in self.crates.iter() {
+            if !goods.contains(box_type) {
+                goods.push(*box_type);
+            }
+        }
+
+        goods
+    }
+
+    /// Returns true if the given world position is in the storage area above the warehouse floor
+    pub fn contains(position: Vec3) -> bool {
+        let floor = WAREHOUSE_LOCATION.y + WAREHOUSE_HALF_EXTENTS.y;
+
+        (position.x - WAREHOUSE_LOCATION.x).abs() <= WAREHOUSE_HALF_EXTENTS.x
+            && position.y >= floor
+            && position.y <= floor + WAREHOUSE_STORAGE_HEIGHT
+    }
+}
+
+pub struct WarehousePlugin;
+
+impl Plugin for WarehousePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Warehouse::default())
+            .add_event::<OnWarehouseOverflow>()
+            .add_enter_system(GameState::Playing, reset_warehouse)
+            .add_system(track_warehouse_inventory.run_in_state(GameState::Playing));
+    }
+}
+
+fn reset_warehouse(mut warehouse: ResMut<Warehouse>) {
+    *warehouse = Warehouse::default();
+}
+
+/// Keeps track of the crates stacked in the warehouse. Once it is full, any further crates
+/// landing in it are toppled off the edge.
+#[allow(clippy::type_complexity)]
+fn track_warehouse_inventory(
+    mut commands: Commands,
+    mut warehouse: ResMut<Warehouse>,
+    mut overflow_events: EventWriter<OnWarehouseOverflow>,
+    mut crates: Query<(Entity, &Transform, &PhysicsCrate, &mut Velocity), Without<ToppledCrate>>,
+    toppled_crates: Query<(Entity, &Transform), With<ToppledCrate>>,
+) {
+    // toppled crates can be stored again once they have left the warehouse
+    for (entity, tx) in toppled_crates.iter() {
+        if !Warehouse::contains(tx.translation) {
+            commands.entity(entity).remove::<ToppledCrate>();
+        }
+    }
+
+    let mut stored = vec![];
+    let mut arrived = vec![];
+
+    for (entity, tx, physics_crate, _) in crates.iter() {
+        if !Warehouse::contains(tx.translation) {
+            continue;
+        }
+
+        if warehouse.crates.iter().any(|(e, _)| *e == entity) {
+            stored.push((entity, physics_crate.box_type));
+        } else {
+            arrived.push((entity, physics_crate.box_type));
+        }
+    }
+
+    // crates that were already in the warehouse keep their space
+    stored.sort_by_key(|(entity, _)| {
+        warehouse
+            .crates
+            .iter()
+            .position(|(stored_entity, _)| stored_entity == entity)
+    });
+
+    for (entity, box_type) in arrived {
+        if stored.len() < warehouse.capacity {
+            stored.push((entity, box_type));
+            continue;
+        }
+
+        info!("Warehouse full, toppling {:?}", box_type);
+
+        if let Ok((_, tx, _, mut velocity)) = crates.get_mut(entity) {
+            let direction = (tx.translation.x - WAREHOUSE_LOCATION.x).signum();
+            velocity.linear = Vec3::new(direction * TOPPLE_VELOCITY.x, TOPPLE_VELOCITY.y, 0.0);
+        }
+
+        commands.entity(entity).insert(ToppledCrate);
+        overflow_events.send(OnWarehouseOverflow);
+    }
+
+    // only mutate when the contents change so the inventory panel doesn't redraw every frame
+    if stored != warehouse.crates {
+        warehouse.crates = stored;
+    }
+}
@@ -0,0 +1,328 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use chrono::{Duration, NaiveDate};
+use heron::PhysicMaterial;
+use iyes_loopless::prelude::{AppLooplessStateExt, IntoConditionalSystem};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        actions::DraggingBox,
+        clock::GameClock,
+        components::{AnimateWithSpeed, Cart, FloatingCrate, PhysicsCrate, Sinking, Wave},
+        crane::CRANE_X,
+        day_night_cycle::{get_start_date, TimeOfDay},
+        seasons::Season,
+        spawners::{CartTypes, CRATE_FRICTION},
+    },
+    GameState, WIDTH,
+};
+
+const WEATHER_SEED: u64 = 8105227730931466019;
+
+/// The number of days of weather shown in the forecast, including today
+pub const FORECAST_DAYS: usize = 3;
+
+/// The hours of the day that the day's weather is in effect
+const WEATHER_START_HOUR: f32 = 6.0;
+const WEATHER_END_HOUR: f32 = 18.0;
+
+/// How far from the dock ships can be seen in fog
+const FOG_VISIBLE_DISTANCE: f32 = 0.35 * WIDTH;
+
+/// How many times faster crates rot in a heatwave
+const HEATWAVE_SPOILAGE_RATE: f64 = 2.0;
+
+/// How fast the horses pull carts in a heatwave, as a proportion of their usual speed
+const HEATWAVE_HORSE_SPEED: f32 = 0.6;
+
+/// How much crates grip the surfaces they rest on in the snow
+const SNOW_CRATE_FRICTION: f32 = 0.0;
+
+/// Event triggered when the weather in effect changes
+pub struct OnWeatherChanged {
+    pub from: Weather,
+    pub to: Weather,
+}
+
+/// Event triggered when a saved game is restored, so the effects of the weather in effect can be
+/// started again without it counting as a change in the weather
+pub struct OnWeatherRestored(pub Weather);
+
+pub struct OnRainStart;
+
+pub struct OnRainEnd;
+
+/// The weather for a single day
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Weather {
+    Sunny,
+    Stormy,
+    Foggy,
+    Heatwave,
+    Snowy,
+}
+
+impl Weather {
+    /// Picks the weather for a day from the given random number generator, using the chances
+    /// of bad weather for the season
+    fn generate(rng: &mut ChaCha8Rng, date: NaiveDate) -> Self {
+        let mut roll = rng.gen_range(0.0..1.0);
+        for (weather, chance) in Season::from_date(date).weather_chances() {
+            if roll < *chance {
+                return *weather;
+            }
+            roll -= chance;
+        }
+
+        Weather::Sunny
+    }
+
+    /// Whether the sun can be seen in this weather
+    pub fn shows_sun(&self) -> bool {
+        matches!(self, Weather::Sunny | Weather::Heatwave)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Weather::Sunny => "Fine",
+            Weather::Stormy => "Storms",
+            Weather::Foggy => "Fog",
+            Weather::Heatwave => "Heat",
+            Weather::Snowy => "Snow",
+        }
+    }
+
+    pub fn colour(&self) -> Color {
+        match self {
+            Weather::Sunny => Color::rgb(1.0, 0.85, 0.3),
+            Weather::Stormy => Color::rgb(0.6, 0.7, 0.9),
+            Weather::Foggy => Color::rgb(0.75, 0.75, 0.75),
+            Weather::Heatwave => Color::rgb(1.0, 0.5, 0.2),
+            Weather::Snowy => Color::rgb(0.9, 0.95, 1.0),
+        }
+    }
+}
+
+/// A resource holding the weather for today and the days ahead. The weather is generated from
+/// a seed, so it can be known in advance and is the same every game.
+pub struct WeatherForecast {
+    rng: ChaCha8Rng,
+
+    /// The date the forecast was first generated for
+    pub started_on: NaiveDate,
+
+    /// The date of the first day in the forecast
+    pub date: NaiveDate,
+
+    /// The weather for each day, starting with today
+    pub days: VecDeque<Weather>,
+}
+
+impl WeatherForecast {
+    pub fn new(seed: u64, date: NaiveDate) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        // the first day is always fine, to ease players in
+        let mut days = VecDeque::from([Weather::Sunny]);
+        while days.len() < FORECAST_DAYS {
+            let weather = Weather::generate(&mut rng, date + Duration::days(days.len() as i64));
+            days.push_back(weather);
+        }
+
+        Self {
+            rng,
+            started_on: date,
+            date,
+            days,
+        }
+    }
+
+    /// Recreates a forecast that was started on one date and has been kept up to date until
+    /// another, giving the same weather as the original forecast
+    pub fn restore(started_on: NaiveDate, date: NaiveDate) -> Self {
+        let mut forecast = Self::new(WEATHER_SEED, started_on);
+        while forecast.date < date {
+            forecast.advance();
+        }
+
+        forecast
+    }
+
+    pub fn today(&self) -> Weather {
+        self.days[0]
+    }
+
+    /// Moves the forecast on to the next day, forecasting another day ahead
+    fn advance(&mut self) {
+        self.days.pop_front();
+        self.date += Duration::days(1);
+
+        let weather = Weather::generate(
+            &mut self.rng,
+            self.date + Duration::days(self.days.len() as i64),
+        );
+        self.days.push_back(weather);
+    }
+}
+
+/// A resource holding the weather currently in effect. The day's weather arrives in the
+/// morning and clears up in the evening.
+pub struct ActiveWeather(pub Weather);
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WeatherForecast::new(WEATHER_SEED, get_start_date()))
+            .insert_resource(ActiveWeather(Weather::Sunny))
+            .add_event::<OnWeatherChanged>()
+            .add_event::<OnWeatherRestored>()
+            .add_event::<OnRainStart>()
+            .add_event::<OnRainEnd>()
+            .add_enter_system(GameState::Playing, reset_weather)
+            .add_system(update_weather.run_in_state(GameState::Playing))
+            .add_system(rain_with_storms.run_on_event::<OnWeatherChanged>())
+            .add_system(hide_ships_in_fog.run_in_state(GameState::Playing))
+            .add_system(spoil_crates_in_heat.run_in_state(GameState::Playing))
+            .add_system(slow_horses_in_heat.run_in_state(GameState::Playing))
+            .add_system(slippery_crates_in_snow.run_in_state(GameState::Playing));
+    }
+}
+
+fn reset_weather(
+    time_of_day: Res<TimeOfDay>,
+    mut forecast: ResMut<WeatherForecast>,
+    mut active: ResMut<ActiveWeather>,
+) {
+    *forecast = WeatherForecast::new(WEATHER_SEED, time_of_day.today);
+    active.0 = Weather::Sunny;
+}
+
+/// Keeps the forecast up to date with the calendar, and changes the weather in effect as the
+/// day's weather arrives and clears
+fn update_weather(
+    time_of_day: Res<TimeOfDay>,
+    mut forecast: ResMut<WeatherForecast>,
+    mut active: ResMut<ActiveWeather>,
+    mut weather_events: EventWriter<OnWeatherChanged>,
+) {
+    while forecast.date < time_of_day.today {
+        forecast.advance();
+    }
+
+    let expected = if (WEATHER_START_HOUR..WEATHER_END_HOUR).contains(&time_of_day.time_of_day) {
+        forecast.today()
+    } else {
+        Weather::Sunny
+    };
+
+    if active.0 != expected {
+        info!("Weather changing from {:?} to {:?}", active.0, expected);
+        weather_events.send(OnWeatherChanged {
+            from: active.0,
+            to: expected,
+        });
+        active.0 = expected;
+    }
+}
+
+fn rain_with_storms(
+    mut weather_events: EventReader<OnWeatherChanged>,
+    mut rain_start_events: EventWriter<OnRainStart>,
+    mut rain_stop_events: EventWriter<OnRainEnd>,
+) {
+    for evt in weather_events.iter() {
+        if evt.from == Weather::Stormy {
+            rain_stop_events.send(OnRainEnd);
+        }
+
+        if evt.to == Weather::Stormy {
+            rain_start_events.send(OnRainStart);
+        }
+    }
+}
+
+/// Hides ships in the fog until they are close to the dock
+#[allow(clippy::type_complexity)]
+fn hide_ships_in_fog(
+    active: Res<ActiveWeather>,
+    mut ships: Query<(&Transform, &mut Visibility), (With<Wave>, Without<Sinking>)>,
+) {
+    for (tx, mut visibility) in ships.iter_mut() {
+        let is_visible =
+            active.0 != Weather::Foggy || (tx.translation.x - CRANE_X).abs() < FOG_VISIBLE_DISTANCE;
+
+        if visibility.is_visible != is_visible {
+            visibility.is_visible = is_visible;
+        }
+    }
+}
+
+/// Rots crates faster in a heatwave, by ageing them faster than time passes
+fn spoil_crates_in_heat(
+    time: Res<GameClock>,
+    active: Res<ActiveWeather>,
+    mut dragging: ResMut<DraggingBox>,
+    mut physics_crates: Query<&mut PhysicsCrate>,
+    mut floating_crates: Query<&mut FloatingCrate>,
+) {
+    if active.0 != Weather::Heatwave {
+        return;
+    }
+
+    let extra_age = (HEATWAVE_SPOILAGE_RATE - 1.0) * time.delta_seconds_f64();
+
+    for mut physics_crate in physics_crates.iter_mut() {
+        physics_crate.spawned_at -= extra_age;
+    }
+
+    for mut floating in floating_crates.iter_mut() {
+        floating.spawned_at -= extra_age;
+    }
+
+    if dragging.box_type.is_some() {
+        dragging.spawned_at -= extra_age;
+    }
+}
+
+/// Slows the horses down in a heatwave
+fn slow_horses_in_heat(
+    active: Res<ActiveWeather>,
+    cart_types: Res<CartTypes>,
+    mut carts: Query<(&Cart, &mut AnimateWithSpeed)>,
+) {
+    let factor = if active.0 == Weather::Heatwave {
+        HEATWAVE_HORSE_SPEED
+    } else {
+        1.0
+    };
+
+    for (cart, mut animation) in carts.iter_mut() {
+        let speed = cart_types.get(cart.cart_type).speed * factor;
+        if animation.speed != speed {
+            animation.speed = speed;
+        }
+    }
+}
+
+/// Makes crates slide about on snowy days
+fn slippery_crates_in_snow(
+    active: Res<ActiveWeather>,
+    mut crates: Query<&mut PhysicMaterial, With<PhysicsCrate>>,
+) {
+    let friction = if active.0 == Weather::Snowy {
+        SNOW_CRATE_FRICTION
+    } else {
+        CRATE_FRICTION
+    };
+
+    for mut material in crates.iter_mut() {
+        if material.friction != friction {
+            material.friction = friction;
+        }
+    }
+}
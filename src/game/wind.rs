@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use heron::{Acceleration, Collisions};
+use iyes_loopless::prelude::{AppLooplessStateExt, IntoConditionalSystem};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    game::{
+        clock::GameClock,
+        components::{AnimateWithSpeed, PhysicsCrate, Ship},
+        spawners::{lanes::ShipLane, ShippingLanes},
+        Animation, OnRainEnd, OnRainStart,
+    },
+    loader::AnimationAssets,
+    GameState,
+};
+
+/// The strongest breeze on a fine day, in pixels per second
+const CALM_WIND_MAX: f32 = 20.0;
+
+/// The range of wind speeds on a stormy day, in pixels per second
+const STORM_WIND_MIN: f32 = 60.0;
+const STORM_WIND_MAX: f32 = 120.0;
+
+/// How quickly the wind moves towards its target speed
+const WIND_CHANGE_RATE: f32 = 0.5;
+
+/// How much of the wind speed is applied to flying crates as an acceleration
+const CRATE_WIND_FACTOR: f32 = 1.5;
+
+/// How much a ship's speed changes per unit of wind, as a proportion of its lane speed
+const SHIP_WIND_FACTOR: f32 = 0.004;
+
+/// How far ships heel over per unit of wind, in radians
+const SHIP_HEEL_FACTOR: f32 = 0.0015;
+
+/// The wind speed above which ships set full sail
+const FULL_SAIL_WIND: f32 = 0.5 * CALM_WIND_MAX;
+
+/// The wind speed above which ships reef their sails to ride out a storm
+const REEFED_SAIL_WIND: f32 = STORM_WIND_MIN;
+
+/// A resource holding the current wind, which changes with the weather
+#[derive(Debug, Default)]
+pub struct Wind {
+    /// The horizontal wind speed, positive when blowing to the right
+    pub velocity: f32,
+
+    /// The speed the wind is moving towards
+    pub target: f32,
+}
+
+impl Wind {
+    /// The acceleration the wind applies to a crate flying through the air
+    pub fn crate_acceleration(&self) -> Vec3 {
+        Vec3::new(self.velocity * CRATE_WIND_FACTOR, 0.0, 0.0)
+    }
+}
+
+pub struct WindPlugin;
+
+impl Plugin for WindPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Wind::default())
+            .add_enter_system(GameState::Playing, reset_wind)
+            .add_system(storm_winds.run_on_event::<OnRainStart>())
+            .add_system(calm_winds.run_on_event::<OnRainEnd>())
+            .add_system(update_wind.run_in_state(GameState::Playing))
+            .add_system(blow_flying_crates.run_in_state(GameState::Playing))
+            .add_system(blow_ships.run_in_state(GameState::Playing))
+            .add_system(heel_ships.run_in_state(GameState::Playing))
+            .add_system(set_sails.run_in_state(GameState::Playing));
+    }
+}
+
+/// Picks a random wind speed in the given range, blowing in a random direction
+fn random_wind(min: f32, max: f32) -> f32 {
+    let mut rng = thread_rng();
+    let direction = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+    direction * rng.gen_range(min..max)
+}
+
+fn reset_wind(mut wind: ResMut<Wind>) {
+    let target = random_wind(0.0, CALM_WIND_MAX);
+    *wind = Wind {
+        velocity: target,
+        target,
+    };
+}
+
+fn storm_winds(mut wind: ResMut<Wind>) {
+    wind.target = random_wind(STORM_WIND_MIN, STORM_WIND_MAX);
+    info!("Storm winds picking up to {:.0}", wind.target);
+}
+
+fn calm_winds(mut wind: ResMut<Wind>) {
+    wind.target = random_wind(0.0, CALM_WIND_MAX);
+    info!("Winds calming to {:.0}", wind.target);
+}
+
+fn update_wind(time: Res<GameClock>, mut wind: ResMut<Wind>) {
+    if (wind.target - wind.velocity).abs() < 0.01 {
+        return;
+    }
+
+    let t = (WIND_CHANGE_RATE * time.delta_seconds()).clamp(0.0, 1.0);
+    wind.velocity += (wind.target - wind.velocity) * t;
+}
+
+/// Pushes crates sideways while they are in the air. Crates resting on something are left alone.
+fn blow_flying_crates(
+    wind: Res<Wind>,
+    mut crates: Query<(&Collisions, &mut Acceleration), With<PhysicsCrate>>,
+) {
+    let flying = wind.crate_acceleration();
+
+    for (collisions, mut acceleration) in crates.iter_mut() {
+        let target = if collisions.is_empty() {
+            flying
+        } else {
+            Vec3::ZERO
+        };
+
+        if acceleration.linear != target {
+            acceleration.linear = target;
+        }
+    }
+}
+
+/// Speeds up ships sailing with the wind, and slows down ships sailing into it
+fn blow_ships(
+    wind: Res<Wind>,
+    lanes: Res<ShippingLanes>,
+    mut ships: Query<(&mut AnimateWithSpeed, &ShipLane)>,
+) {
+    for (mut animation, ship_lane) in ships.iter_mut() {
+        let lane = match lanes.0.get(ship_lane.lane) {
+            Some(lane) => lane,
+            None => continue,
+        };
+
+        let tailwind = wind.velocity * ship_lane.direction.sign();
+        animation.speed = lane.speed * (1.0 + SHIP_WIND_FACTOR * tailwind).max(0.25);
+    }
+}
+
+/// Leans ships over as the wind fills their sails
+fn heel_ships(
+    time: Res<GameClock>,
+    wind: Res<Wind>,
+    mut ships: Query<(&mut Transform, &mut Ship)>,
+) {
+    let target = -wind.velocity * SHIP_HEEL_FACTOR;
+    let t = (WIND_CHANGE_RATE * time.delta_seconds()).clamp(0.0, 1.0);
+
+    for (mut tx, mut ship) in ships.iter_mut() {
+        let heel = ship.heel + (target - ship.heel) * t;
+        tx.rotate_z(heel - ship.heel);
+        ship.heel = heel;
+    }
+}
+
+/// Switches the ships' sail animations with the strength of the wind, from idle in light airs
+/// to full sail in a breeze, and reefed in a storm
+fn set_sails(
+    wind: Res<Wind>,
+    animations: Res<AnimationAssets>,
+    mut ships: Query<&mut Handle<Animation>, With<Ship>>,
+) {
+    let strength = wind.velocity.abs();
+    let sails = if strength >= REEFED_SAIL_WIND {
+        &animations.ship_reefed
+    } else if strength >= FULL_SAIL_WIND {
+        &animations.ship_full_sail
+    } else {
+        &animations.ship_idle
+    };
+
+    for mut animation in ships.iter_mut() {
+        if *animation != *sails {
+            *animation = sails.clone();
+        }
+    }
+}
@@ -22,6 +22,16 @@ impl Plugin for InputPlugin {
 pub enum PlayerActions {
     Click,
     Proceed,
+    ChangeMode,
+    Build,
+    Rotate,
+    ToggleThrowAssist,
+    Pause,
+    NormalSpeed,
+    DoubleSpeed,
+    QuadrupleSpeed,
+    Continue,
+    SaveAndQuit,
 }
 
 /// Initialises the input manager, linking commands
@@ -30,6 +40,16 @@ fn init_input_manager(mut commands: Commands) {
 
     input_map.insert(MouseButton::Left, PlayerActions::Click);
     input_map.insert(KeyCode::Space, PlayerActions::Proceed);
+    input_map.insert(KeyCode::M, PlayerActions::ChangeMode);
+    input_map.insert(KeyCode::B, PlayerActions::Build);
+    input_map.insert(KeyCode::R, PlayerActions::Rotate);
+    input_map.insert(KeyCode::T, PlayerActions::ToggleThrowAssist);
+    input_map.insert(KeyCode::P, PlayerActions::Pause);
+    input_map.insert(KeyCode::Key1, PlayerActions::NormalSpeed);
+    input_map.insert(KeyCode::Key2, PlayerActions::DoubleSpeed);
+    input_map.insert(KeyCode::Key3, PlayerActions::QuadrupleSpeed);
+    input_map.insert(KeyCode::C, PlayerActions::Continue);
+    input_map.insert(KeyCode::Escape, PlayerActions::SaveAndQuit);
     commands
         .spawn()
         .insert_bundle(InputManagerBundle::<PlayerActions> {
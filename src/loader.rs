@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
-use crate::{game::Animation, GameState};
+use crate::{
+    game::{calendar::CalendarEvents, Animation},
+    GameState,
+};
 
 /// A plugin that automatically loads in fonts, audio assets, textures, animations, maps etc
 /// from file. See the various asset collection classes for the assets being loaded
@@ -15,6 +18,7 @@ impl Plugin for LoadingPlugin {
                 .with_collection::<AudioAssets>()
                 .with_collection::<TextureAssets>()
                 .with_collection::<AnimationAssets>()
+                .with_collection::<CalendarAssets>()
                 .continue_to_state(GameState::Menu),
         );
     }
@@ -72,6 +76,14 @@ pub struct TextureAssets {
     #[asset(path = "textures/horse_and_cart.png")]
     pub horse_and_cart: Handle<TextureAtlas>,
 
+    #[asset(texture_atlas(tile_size_x = 160., tile_size_y = 64., columns = 3, rows = 1))]
+    #[asset(path = "textures/hand_barrow.png")]
+    pub hand_barrow: Handle<TextureAtlas>,
+
+    #[asset(texture_atlas(tile_size_x = 160., tile_size_y = 64., columns = 3, rows = 1))]
+    #[asset(path = "textures/wagon.png")]
+    pub wagon: Handle<TextureAtlas>,
+
     #[asset(path = "textures/cart_shadow.png")]
     pub cart_shadow: Handle<Image>,
 
@@ -101,10 +113,6 @@ pub struct TextureAssets {
     #[asset(path = "textures/crates.png")]
     pub crates: Handle<TextureAtlas>,
 
-    #[asset(texture_atlas(tile_size_x = 160., tile_size_y = 64., columns = 2, rows = 5))]
-    #[asset(path = "textures/cart_boxes.png")]
-    pub cart_boxes: Handle<TextureAtlas>,
-
     #[asset(path = "textures/box_type_glassware.png")]
     pub box_type_glassware: Handle<Image>,
 
@@ -135,7 +143,7 @@ pub struct TextureAssets {
     #[asset(path = "textures/box_type_wine2.png")]
     pub box_type_wine2: Handle<Image>,
 
-    #[asset(texture_atlas(tile_size_x = 288., tile_size_y = 224., columns = 1, rows = 1))]
+    #[asset(texture_atlas(tile_size_x = 288., tile_size_y = 224., columns = 3, rows = 1))]
     #[asset(path = "textures/ship.png")]
     pub ship: Handle<TextureAtlas>,
 
@@ -218,6 +226,12 @@ pub struct AnimationAssets {
     #[asset(path = "animations/ship_idle.animation.yml")]
     pub ship_idle: Handle<Animation>,
 
+    #[asset(path = "animations/ship_full_sail.animation.yml")]
+    pub ship_full_sail: Handle<Animation>,
+
+    #[asset(path = "animations/ship_reefed.animation.yml")]
+    pub ship_reefed: Handle<Animation>,
+
     #[asset(path = "animations/splashes.animation.yml")]
     pub splashes: Handle<Animation>,
 
@@ -230,3 +244,10 @@ pub struct AnimationAssets {
     #[asset(path = "animations/factory_tutorial.animation.yml")]
     pub factory_tutorial: Handle<Animation>,
 }
+
+/// Contains the dated events that change trade, loaded using a custom calendar loader
+#[derive(AssetCollection)]
+pub struct CalendarAssets {
+    #[asset(path = "calendar/1883.calendar.yml")]
+    pub events_1883: Handle<CalendarEvents>,
+}